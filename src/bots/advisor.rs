@@ -0,0 +1,88 @@
+use std::fmt::{Display, Formatter, Result};
+
+use super::tracker::Tracker;
+use crate::common::{
+    action::Action,
+    card::{Card, CardActions, CardGetters, CardSuitsGetters, BIG_RANK, LITTLE_RANK},
+    error::TarotError,
+    evaluation::evaluate_hand,
+    locale::{fill, text, Messages},
+    player::{Player, PlayerActions},
+    trick::{TrickActions, TrickGetters},
+    view::SeatView,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint {
+    pub action: Action,
+    pub reason: String,
+}
+impl Display for Hint {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}: {}", self.action, self.reason)
+    }
+}
+
+// The advice is what the bot of the same level would do in the seat of the player
+pub fn advise(player: &Player, view: &SeatView) -> std::result::Result<Hint, TarotError> {
+    let mut bot = player.clone();
+    bot.is_human = false;
+    let action = bot.act(view)?;
+    Ok(Hint {
+        action,
+        reason: explain(view, &action),
+    })
+}
+
+pub fn explain(view: &SeatView, action: &Action) -> String {
+    let trumps: Vec<&Card> = view.hand.iter().filter(|c| c.suit.is_trump()).collect();
+    let oudlers = view.hand.iter().filter(|card| card.is_oudler()).count();
+    match action {
+        Action::Bid(_) => fill(
+            Messages::ReasonBid,
+            &[
+                &oudlers,
+                &trumps.iter().filter(|card| !card.is_fool()).count(),
+                &format!("{:.0}", evaluate_hand(&view.hand)),
+            ],
+        ),
+        Action::CallKing(king) if view.hand.contains(king) => {
+            String::from(text(Messages::ReasonCallOwnKing))
+        }
+        Action::CallKing(king) => fill(Messages::ReasonCallKing, &[king]),
+        Action::Discard(card) => {
+            let suit = view.hand.iter().filter(|c| c.suit.name == card.suit.name);
+            match suit.count() {
+                1 => fill(Messages::ReasonLastOfSuit, &[&card.suit.name.label()]),
+                _ => fill(Messages::ReasonCheapest, &[&card.score()]),
+            }
+        }
+        Action::Declare(Some(_)) => fill(Messages::ReasonHandle, &[&trumps.len()]),
+        Action::Declare(None) => String::from(text(Messages::ReasonNoHandle)),
+        Action::Play(card) => explain_play(view, card),
+    }
+}
+
+fn explain_play(view: &SeatView, card: &Card) -> String {
+    let tracker = Tracker::new(view);
+    let played_suit = view.trick.played_suit();
+    let best = view
+        .trick
+        .get_best_played_card_index(played_suit)
+        .map(|index| view.trick.played_cards[index]);
+    let has_little = view.hand.iter().any(|c| c.is_petit());
+    let wins = best.is_none_or(|best| card.is_superior_than(&best, played_suit));
+
+    let reason = match (card.is_fool(), card.rank, wins && tracker.is_master(card)) {
+        (true, _, _) => Messages::ReasonFool,
+        (false, LITTLE_RANK, true) if card.suit.is_trump() => Messages::ReasonSafePetit,
+        (false, BIG_RANK, true) if card.suit.is_trump() && has_little => {
+            Messages::ReasonBigSavesPetit
+        }
+        (false, _, true) => Messages::ReasonMaster,
+        _ if view.legal_cards().len() == 1 => Messages::ReasonOnlyCard,
+        _ if best.is_none() => Messages::ReasonLeadLow,
+        _ => Messages::ReasonLostTrick,
+    };
+    String::from(text(reason))
+}
@@ -0,0 +1,261 @@
+use std::fmt::{Display, Formatter, Result};
+
+use super::solver::Solver;
+use crate::common::{
+    action::Action,
+    bid::Bids,
+    card::{Card, CardGetters, CardSuitsGetters, KING_RANK},
+    deal::{recorded_actions, Deal},
+    error::TarotError,
+    evaluation::{evaluate_hand, ideal_bid},
+    hand::Side,
+    locale::{fill, Messages},
+    notation::DealRecord,
+    phase::Phase,
+    player::PlayerActions,
+    score::{multiplier, CONTRACT_POINTS, PETIT_AU_BOUT_POINTS},
+    trick::TrickGetters,
+    utils::subtract,
+};
+
+// Losing an oudler raises the points needed by the attack by about this much
+const OUDLER_POINTS: f64 = 5.0;
+// The end of the deal is solved with all the hands open
+const SOLVED_CARDS: usize = 5;
+const PLAY_MARGIN: f64 = 2.0;
+const ECART_MARGIN: f64 = 6.0;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Mistakes {
+    Overbid,
+    Underbid,
+    Ecart,
+    LostPetit,
+    LostKing,
+    Play,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mistake {
+    pub seat: usize,
+    pub kind: Mistakes,
+    pub description: String,
+    // Estimated points lost by the side of the player
+    pub cost: f64,
+}
+impl Display for Mistake {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let cost = format!("{:.1}", self.cost);
+        write!(
+            f,
+            "{}",
+            fill(Messages::MistakeCost, &[&self.description, &cost])
+        )
+    }
+}
+
+// Risky cards played when something else was possible, checked once their trick is over
+struct Exposed {
+    trick: usize,
+    seat: usize,
+    card: Card,
+}
+
+// Replays the recorded deal and reviews every decision of the given seats
+pub fn analyze(
+    record: &DealRecord,
+    seats: &[usize],
+) -> std::result::Result<Vec<Mistake>, TarotError> {
+    let mut deal = Deal::from_record(&DealRecord {
+        auction: Vec::new(),
+        called_king: None,
+        ecart: Vec::new(),
+        handles: Vec::new(),
+        tricks: Vec::new(),
+        ..record.clone()
+    })?;
+    let mut mistakes = Vec::new();
+    let mut exposed = Vec::new();
+    for action in recorded_actions(record) {
+        let seat = deal.current;
+        if seats.contains(&seat) {
+            match action {
+                Action::Bid(bid) => mistakes.extend(review_bid(&deal, bid)),
+                Action::Discard(_) if deal.record.ecart.is_empty() => {
+                    mistakes.extend(review_ecart(&deal, &record.ecart)?)
+                }
+                Action::Play(card) => {
+                    if is_exposed(&card) && deal.legal_actions().len() > 1 {
+                        exposed.push(Exposed {
+                            trick: deal.tricks.len(),
+                            seat,
+                            card,
+                        });
+                    }
+                    mistakes.extend(review_play(&deal, card)?);
+                }
+                _ => {}
+            }
+        }
+        deal.apply(action)?;
+    }
+    mistakes.extend(
+        exposed
+            .iter()
+            .filter_map(|exposed| review_exposed(&deal, exposed)),
+    );
+    Ok(mistakes)
+}
+
+fn review_bid(deal: &Deal, bid: Bids) -> Option<Mistake> {
+    let hand = &deal.current_player().hand.cards;
+    let ideal = ideal_bid(hand, deal.players.len());
+    let available = deal.legal_actions().contains(&Action::Bid(ideal));
+    let kind = match level(bid).cmp(&level(ideal)) {
+        std::cmp::Ordering::Greater => Mistakes::Overbid,
+        std::cmp::Ordering::Less if available => Mistakes::Underbid,
+        _ => return None,
+    };
+    Some(Mistake {
+        seat: deal.current,
+        kind,
+        description: fill(
+            Messages::MistakeBid,
+            &[&bid, &ideal, &format!("{:.0}", evaluate_hand(hand))],
+        ),
+        cost: (multiplier(&bid) - multiplier(&ideal)).abs() * CONTRACT_POINTS,
+    })
+}
+
+// The bot composes its own écart from the same hand to compare what is left
+fn review_ecart(deal: &Deal, ecart: &[Card]) -> std::result::Result<Option<Mistake>, TarotError> {
+    let seat = deal.current;
+    let mut bot = deal.clone();
+    bot.events = Default::default();
+    bot.players[seat].is_human = false;
+    while bot.phase == Phase::Ecart {
+        let action = bot.current_player().act(&bot.view(seat))?;
+        bot.apply(action)?;
+    }
+
+    let mut hand = deal.players[seat].hand.cards.clone();
+    subtract(&mut hand, ecart);
+    let cost = evaluate_hand(&bot.players[seat].hand.cards) - evaluate_hand(&hand);
+    Ok((cost >= ECART_MARGIN).then(|| Mistake {
+        seat,
+        kind: Mistakes::Ecart,
+        description: fill(
+            Messages::MistakeEcart,
+            &[&cards(ecart), &cards(&bot.record.ecart)],
+        ),
+        cost,
+    }))
+}
+
+fn review_play(deal: &Deal, card: Card) -> std::result::Result<Option<Mistake>, TarotError> {
+    let seat = deal.current;
+    if deal.players[seat].hand.cards.len() > SOLVED_CARDS {
+        return Ok(None);
+    }
+    let mut solver = Solver::default();
+    let mut value = |card: Card| -> std::result::Result<f64, TarotError> {
+        let mut next = deal.clone();
+        next.events = Default::default();
+        next.apply(Action::Play(card))?;
+        let rest = match next.phase {
+            Phase::Play => solver.solve(&next)?.attack,
+            _ => 0.0,
+        };
+        Ok(attack_points(&next) + rest)
+    };
+    let mut values = Vec::new();
+    for action in deal.legal_actions() {
+        if let Action::Play(legal) = action {
+            values.push((legal, value(legal)?));
+        }
+    }
+    let sign = match deal.side(seat) {
+        Side::Attack => 1.0,
+        Side::Defense => -1.0,
+    };
+    let played = values
+        .iter()
+        .find(|(legal, _)| *legal == card)
+        .map(|(_, v)| *v);
+    let best = values
+        .iter()
+        .max_by(|a, b| (sign * a.1).total_cmp(&(sign * b.1)))
+        .copied();
+    let (Some(played), Some((best, value))) = (played, best) else {
+        return Ok(None);
+    };
+    let cost = sign * (value - played);
+    Ok((cost >= PLAY_MARGIN).then(|| Mistake {
+        seat,
+        kind: Mistakes::Play,
+        description: fill(Messages::MistakePlay, &[&card, &best]),
+        cost,
+    }))
+}
+
+fn review_exposed(deal: &Deal, exposed: &Exposed) -> Option<Mistake> {
+    let trick = deal.tricks.get(exposed.trick)?;
+    let winner = trick.winner(deal.players.len())?;
+    if deal.side(winner) == deal.side(exposed.seat) {
+        return None;
+    }
+    let is_little = exposed.card.is_petit();
+    let is_last = exposed.trick + 1 == deal.tricks.len();
+    let (kind, cost) = match (is_little, is_last) {
+        (true, true) => {
+            let bid = deal
+                .taker
+                .as_ref()
+                .map(|taker| taker.bid)
+                .unwrap_or_default();
+            let au_bout = 2.0 * PETIT_AU_BOUT_POINTS * multiplier(&bid);
+            (
+                Mistakes::LostPetit,
+                exposed.card.score() + OUDLER_POINTS + au_bout,
+            )
+        }
+        (true, false) => (Mistakes::LostPetit, exposed.card.score() + OUDLER_POINTS),
+        (false, _) => (Mistakes::LostKing, exposed.card.score()),
+    };
+    Some(Mistake {
+        seat: exposed.seat,
+        kind,
+        description: fill(
+            Messages::MistakeLost,
+            &[
+                &exposed.card,
+                &deal.record.players[winner],
+                &(exposed.trick + 1),
+            ],
+        ),
+        cost,
+    })
+}
+
+fn is_exposed(card: &Card) -> bool {
+    card.is_petit() || (!card.suit.is_trump() && card.rank == KING_RANK)
+}
+
+fn attack_points(deal: &Deal) -> f64 {
+    (0..deal.players.len())
+        .filter(|seat| deal.side(*seat) == Side::Attack)
+        .flat_map(|seat| &deal.players[seat].hand.won_cards)
+        .map(|card| card.score())
+        .sum()
+}
+
+fn level(bid: Bids) -> usize {
+    Bids::AVAILABLE_BIDS
+        .iter()
+        .position(|available| *available == bid)
+        .map_or(0, |index| index + 1)
+}
+
+fn cards(cards: &[Card]) -> String {
+    cards.iter().map(|card| card.to_string()).collect()
+}
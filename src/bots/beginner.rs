@@ -0,0 +1,15 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::common::{action::Action, bid::Bid, error::TarotError, phase::Phase, view::SeatView};
+
+// A beginner bids on its cards like everybody, then plays any legal move
+pub fn act<R: Rng>(view: &SeatView, rng: &mut R) -> Result<Action, TarotError> {
+    if view.phase == Phase::Auction {
+        let mut bid = Bid::new(view.contract());
+        return Ok(Action::Bid(bid.bot_choose(&view.hand, view.n_players())));
+    }
+    view.legal_actions
+        .choose(rng)
+        .copied()
+        .ok_or(TarotError::NoAvailableChoice)
+}
@@ -0,0 +1,27 @@
+use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum Difficulties {
+    Beginner,
+    #[default]
+    Intermediate,
+    Expert,
+}
+impl Display for Difficulties {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl FromStr for Difficulties {
+    type Err = String;
+
+    fn from_str(name: &str) -> std::result::Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "beginner" => Ok(Self::Beginner),
+            "intermediate" => Ok(Self::Intermediate),
+            "expert" => Ok(Self::Expert),
+            _ => Err(format!("Unknown difficulty '{}'", name)),
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! The engine protocol, to seat any external program at the table.
+//!
+//! Like UCI for chess, an engine is a subprocess reading commands on its standard
+//! input and answering on its standard output, one line at a time. Cards are written
+//! with their `CardGetters::id`, bids and handles with their notation names, and
+//! seats are indexes in the `players` line, starting at 0 with the first player to bid.
+//!
+//! The engine first answers `tarot` with its name and `tarotok`. For every decision,
+//! it then receives its whole view of the deal between `position` and `go`, the
+//! `go` line naming the request (`bid`, `call`, `discard`, `declare` or `play`)
+//! and the legal choices. The engine answers with the request and its choice, any
+//! other answer is refused with `illegal` and the request sent again. Lines starting
+//! with `info` are ignored, and `quit` ends the game. An engine that does not answer
+//! in time is stopped.
+//!
+//! ```text
+//! > tarot
+//! < id name MyBot
+//! < tarotok
+//! > position
+//! > seat 1
+//! > players Player 1, MyBot, Player 3, Player 4
+//! > hand C3 C7 H14 T21 ...
+//! > auction 0:Passe
+//! > go bid Petite Garde GardeSans GardeContre Passe
+//! < bid Garde
+//! > position
+//! > ...
+//! > trick 0 C3 C5 C14 T2
+//! > current 3 T21 T4
+//! > go play T9 T11
+//! < play C7
+//! > illegal C7 is not legal
+//! > position
+//! > ...
+//! > go play T9 T11
+//! < play T11
+//! > quit
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::common::{
+    action::Action,
+    card::{Card, CardGetters},
+    error::TarotError,
+    phase::Phase,
+    view::SeatView,
+};
+
+// Answers refused in a row before giving up on the engine
+pub const MAX_ATTEMPTS: usize = 3;
+// Time given to the engine for every answer, its introduction included
+pub const ANSWER_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub struct Engine {
+    pub command: String,
+    pub name: String,
+    pub timeout: Duration,
+    child: Child,
+    // The lines of the engine, read on their own thread so a silent engine cannot block the game
+    lines: Receiver<String>,
+    // Closed when the engine quits, for the engines only stopping at the end of their input
+    writer: Option<ChildStdin>,
+}
+impl Debug for Engine {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Engine({})", self.command)
+    }
+}
+impl Engine {
+    // The command is split on whitespace, like `python3 mybot.py`
+    pub fn launch(command: &str) -> Result<Self, TarotError> {
+        Self::launch_with_timeout(command, ANSWER_TIMEOUT)
+    }
+
+    pub fn launch_with_timeout(command: &str, timeout: Duration) -> Result<Self, TarotError> {
+        let mut words = command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or_else(|| TarotError::Protocol(String::from("The engine command is empty")))?;
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let (Some(writer), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(TarotError::Protocol(String::from(
+                "The engine has no pipes",
+            )));
+        };
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || read_lines(stdout, sender));
+        let mut engine = Engine {
+            command: command.to_string(),
+            name: command.to_string(),
+            timeout,
+            child,
+            lines,
+            writer: Some(writer),
+        };
+        engine.send("tarot")?;
+        loop {
+            let line = engine.receive()?;
+            match line.strip_prefix("id name ") {
+                Some(name) => engine.name = name.trim().to_string(),
+                None if line == "tarotok" => return Ok(engine),
+                None => {}
+            }
+        }
+    }
+
+    // Asks until the engine answers with a legal action
+    pub fn ask(&mut self, view: &SeatView) -> Result<Action, TarotError> {
+        for _ in 0..MAX_ATTEMPTS {
+            self.send(&position(view))?;
+            let answer = self.receive()?;
+            let reason = match parse_answer(&answer, view) {
+                Ok(action) if view.legal_actions.contains(&action) => return Ok(action),
+                Ok(action) => format!("{} is not legal", choice(&action)),
+                Err(reason) => reason,
+            };
+            self.send(&format!("illegal {}", reason))?;
+        }
+        Err(TarotError::Protocol(format!(
+            "{} gave no legal answer",
+            self.name
+        )))
+    }
+
+    fn send(&mut self, lines: &str) -> Result<(), TarotError> {
+        let writer = self
+            .writer
+            .as_mut()
+            .ok_or_else(|| TarotError::Protocol(format!("{} has quit", self.name)))?;
+        writeln!(writer, "{}", lines)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    // The next line of the engine, skipping its information
+    fn receive(&mut self) -> Result<String, TarotError> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(left) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    self.writer = None;
+                    let _ = self.child.kill();
+                    return Err(TarotError::Protocol(format!(
+                        "{} did not answer in time",
+                        self.name
+                    )));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(TarotError::Protocol(format!("{} has stopped", self.name)));
+                }
+            };
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with("info") {
+                return Ok(line.to_string());
+            }
+        }
+    }
+}
+impl Drop for Engine {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        self.writer = None;
+        // An engine still running after its time to answer is stopped
+        let deadline = Instant::now() + self.timeout;
+        while let Ok(None) = self.child.try_wait() {
+            if Instant::now() >= deadline {
+                let _ = self.child.kill();
+                let _ = self.child.wait();
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+fn read_lines(stdout: ChildStdout, lines: Sender<String>) {
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if lines.send(line).is_err() {
+            break;
+        }
+    }
+}
+
+// The name of the decision asked in a phase
+pub fn request(phase: Phase) -> &'static str {
+    match phase {
+        Phase::Auction => "bid",
+        Phase::KingCall => "call",
+        Phase::Ecart => "discard",
+        Phase::Declarations => "declare",
+        _ => "play",
+    }
+}
+
+// Everything the seat knows, from `position` to `go`
+pub fn position(view: &SeatView) -> String {
+    let mut lines = vec![
+        String::from("position"),
+        format!("seat {}", view.seat),
+        format!("players {}", view.players.join(", ")),
+        format!("hand {}", ids(&view.hand)),
+    ];
+    let auction = view
+        .auction
+        .iter()
+        .map(|(seat, bid)| format!("{}:{}", seat, bid.id()))
+        .collect::<Vec<String>>();
+    lines.push(format!("auction {}", auction.join(" ")));
+    if let Some(taker) = &view.taker {
+        lines.push(format!("taker {}:{}", taker.seat, taker.bid.id()));
+    }
+    if let Some(king) = view.called_king {
+        lines.push(format!("king {}", king.id()));
+    }
+    if let Some(kitty) = &view.kitty {
+        lines.push(format!("kitty {}", ids(kitty)));
+    }
+    if !view.ecart.is_empty() {
+        lines.push(format!("ecart {}", ids(&view.ecart)));
+    }
+    for handle in &view.handles {
+        let cards = ids(&handle.cards);
+        lines.push(format!("handle {} {} {}", handle.seat, handle.kind, cards));
+    }
+    for trick in &view.tricks {
+        let cards = ids(&trick.played_cards);
+        lines.push(format!("trick {} {}", trick.leader, cards));
+    }
+    let cards = ids(&view.trick.played_cards);
+    lines.push(format!("current {} {}", view.trick.leader, cards));
+
+    let choices = view
+        .legal_actions
+        .iter()
+        .map(choice)
+        .collect::<Vec<String>>();
+    lines.push(format!("go {} {}", request(view.phase), choices.join(" ")));
+    lines
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+// Reads an answer like `play T21`, without checking it is legal
+pub fn parse_answer(line: &str, view: &SeatView) -> Result<Action, String> {
+    let (kind, value) = line.split_once(' ').unwrap_or((line, ""));
+    let expected = request(view.phase);
+    if kind != expected {
+        return Err(format!("Expected {}, found '{}'", expected, line));
+    }
+    let card = || value.parse::<Card>();
+    let action = match view.phase {
+        Phase::Auction => Action::Bid(value.parse()?),
+        Phase::KingCall => Action::CallKing(card()?),
+        Phase::Ecart => Action::Discard(card()?),
+        Phase::Declarations if value == "none" => Action::Declare(None),
+        Phase::Declarations => Action::Declare(Some(value.parse()?)),
+        _ => Action::Play(card()?),
+    };
+    Ok(action)
+}
+
+fn choice(action: &Action) -> String {
+    match action {
+        Action::Bid(bid) => bid.id(),
+        Action::Declare(None) => String::from("none"),
+        Action::Declare(Some(handle)) => handle.to_string(),
+        Action::CallKing(card) | Action::Discard(card) | Action::Play(card) => card.id(),
+    }
+}
+
+fn ids(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|card| card.id())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
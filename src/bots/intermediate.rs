@@ -0,0 +1,39 @@
+use super::tracker::Tracker;
+use crate::common::{
+    card::{Card, CardActions, CardGetters, CardSuits, CardSuitsGetters},
+    error::TarotError,
+    trick::{TrickActions, TrickGetters},
+    view::SeatView,
+};
+
+// Takes the trick with its best master card when nobody left can cut it, else gives its cheapest card
+pub fn play(view: &SeatView) -> Result<Card, TarotError> {
+    let cards = view.legal_cards();
+    let tracker = Tracker::new(view);
+    let played_suit = view.trick.played_suit();
+    let best = view
+        .trick
+        .get_best_played_card_index(played_suit)
+        .map(|index| view.trick.played_cards[index]);
+    let remaining: Vec<usize> = (1..view.n_players() - view.trick.played_cards.len())
+        .map(|offset| (view.seat + offset) % view.n_players())
+        .collect();
+
+    let winner = cards
+        .iter()
+        .filter(|card| tracker.is_master(card))
+        .filter(|card| best.is_none_or(|best| card.is_superior_than(&best, played_suit)))
+        .filter(|card| best.is_some() || card.score() > 1.0)
+        .filter(|card| {
+            card.suit.is_trump()
+                || remaining.iter().all(|seat| {
+                    !tracker.is_void(*seat, card.suit.name)
+                        || tracker.is_void(*seat, CardSuits::Trumps)
+                })
+        })
+        .max_by(|a, b| a.score().total_cmp(&b.score()).then(b.rank.cmp(&a.rank)));
+    match winner {
+        Some(card) => Ok(*card),
+        None => view.trick.bot_play(&view.hand, &cards),
+    }
+}
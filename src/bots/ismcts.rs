@@ -0,0 +1,164 @@
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::{sampler::determinize, solver::Solver};
+use crate::common::{
+    action::Action,
+    card::Card,
+    deal::{Deal, DealActions},
+    error::TarotError,
+    phase::Phase,
+    trick::{allowed_cards_to_play, TrickActions},
+    view::SeatView,
+};
+
+pub const DEFAULT_ITERATIONS: usize = 400;
+const EXPLORATION: f64 = 0.7;
+const SCORE_SCALE: f64 = 100.0;
+const ENDGAME_CARDS: usize = 2;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Budget {
+    Iterations(usize),
+    Time(Duration),
+}
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::Iterations(DEFAULT_ITERATIONS)
+    }
+}
+
+// With a seed and an iteration budget, the same view always gives the same card
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct SearchConfig {
+    pub budget: Budget,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    card: Option<Card>,
+    seat: usize,
+    children: Vec<usize>,
+    visits: f64,
+    availability: f64,
+    reward: f64,
+}
+impl Node {
+    fn ucb(&self) -> f64 {
+        self.reward / self.visits + EXPLORATION * (self.availability.ln() / self.visits).sqrt()
+    }
+}
+
+// Single observer ISMCTS: every iteration guesses the hidden cards, then walks the shared tree
+pub fn search(view: &SeatView, config: &SearchConfig) -> Result<Card, TarotError> {
+    let cards = view.legal_cards();
+    if cards.len() <= 1 {
+        return cards.first().copied().ok_or(TarotError::NoAvailableChoice);
+    }
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let start = Instant::now();
+    let mut tree = vec![Node::default()];
+    let mut iterations = 0;
+    while match config.budget {
+        Budget::Iterations(max) => iterations < max,
+        Budget::Time(duration) => iterations == 0 || start.elapsed() < duration,
+    } {
+        let mut deal = determinize(view, &mut rng)?;
+        iterate(&mut tree, &mut deal, &mut rng)?;
+        iterations += 1;
+    }
+
+    tree[0]
+        .children
+        .iter()
+        .map(|child| &tree[*child])
+        .max_by(|a, b| a.visits.total_cmp(&b.visits))
+        .and_then(|node| node.card)
+        .ok_or(TarotError::NoAvailableChoice)
+}
+
+fn iterate<R: Rng>(tree: &mut Vec<Node>, deal: &mut Deal, rng: &mut R) -> Result<(), TarotError> {
+    let mut node = 0;
+    let mut path = vec![node];
+    while deal.phase == Phase::Play {
+        let cards = legal_cards(deal);
+        let mut untried = cards.clone();
+        for child in tree[node].children.clone() {
+            let card = tree[child].card;
+            if card.is_some_and(|card| cards.contains(&card)) {
+                tree[child].availability += 1.0;
+                untried.retain(|untried| Some(*untried) != card);
+            }
+        }
+
+        if let Some(card) = untried.choose(rng).copied() {
+            tree.push(Node {
+                card: Some(card),
+                seat: deal.current,
+                availability: 1.0,
+                ..Default::default()
+            });
+            let child = tree.len() - 1;
+            tree[node].children.push(child);
+            deal.apply(Action::Play(card))?;
+            path.push(child);
+            break;
+        }
+
+        let Some(child) = tree[node]
+            .children
+            .iter()
+            .copied()
+            .filter(|child| tree[*child].card.is_some_and(|card| cards.contains(&card)))
+            .max_by(|a, b| tree[*a].ucb().total_cmp(&tree[*b].ucb()))
+        else {
+            break;
+        };
+        if let Some(card) = tree[child].card {
+            deal.apply(Action::Play(card))?;
+        }
+        node = child;
+        path.push(child);
+    }
+
+    let scores = rollout(deal)?;
+    for node in path {
+        let node = &mut tree[node];
+        node.visits += 1.0;
+        if node.card.is_some() {
+            node.reward += 0.5 + 0.5 * (scores[node.seat] / SCORE_SCALE).tanh();
+        }
+    }
+    Ok(())
+}
+
+fn legal_cards(deal: &Deal) -> Vec<Card> {
+    allowed_cards_to_play(&deal.trick, &deal.current_player().hand.cards)
+}
+
+// The remaining tricks are played with the cheapest legal card (Trick::bot_play) straight
+// on the deal, which keeps rollouts fast, and the last ones are solved with all the hands open
+fn rollout(deal: &mut Deal) -> Result<Vec<f64>, TarotError> {
+    let mut solver = Solver::default();
+    while deal.phase == Phase::Play {
+        let card = match deal.current_player().hand.cards.len() <= ENDGAME_CARDS {
+            true => solver.solve(deal)?.best,
+            false => None,
+        };
+        let card = match card {
+            Some(card) => card,
+            None => deal.trick.bot_play(&[], &legal_cards(deal))?,
+        };
+        deal.apply(Action::Play(card))?;
+    }
+    deal.compute_score()?;
+    Ok(deal
+        .score
+        .as_ref()
+        .map_or(Vec::new(), |score| score.scores.clone()))
+}
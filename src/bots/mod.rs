@@ -0,0 +1,10 @@
+pub mod advisor;
+pub mod analysis;
+pub mod beginner;
+pub mod difficulty;
+pub mod engine;
+pub mod intermediate;
+pub mod ismcts;
+pub mod sampler;
+pub mod solver;
+pub mod tracker;
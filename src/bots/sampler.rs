@@ -0,0 +1,119 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::tracker::{played_cards, Tracker};
+use crate::common::{
+    card::Card,
+    deal::Deal,
+    error::TarotError,
+    notation::{DealRecord, RecordedTrick},
+    phase::Phase,
+    utils::subtract,
+    view::SeatView,
+};
+
+const MAX_ATTEMPTS: usize = 100;
+
+// Cards a seat may still hold, the last slot being the kitty or the ecart hidden to the seat
+#[derive(Debug, Clone)]
+struct Slots {
+    capacities: Vec<usize>,
+    allowed: Vec<(Card, Vec<usize>)>,
+}
+
+// Deals the unseen cards to the other seats and replays the deal up to the view
+pub fn determinize<R: Rng>(view: &SeatView, rng: &mut R) -> Result<Deal, TarotError> {
+    if view.phase != Phase::Play {
+        return Err(TarotError::InvalidPhase {
+            expected: Phase::Play,
+            found: view.phase,
+        });
+    }
+    let slots = slots(view)?;
+    for _ in 0..MAX_ATTEMPTS {
+        let Some(assigned) = assign(&slots, rng) else {
+            continue;
+        };
+        if let Ok(deal) = Deal::from_record(&record(view, assigned)) {
+            return Ok(deal);
+        }
+    }
+    Err(TarotError::NoAvailableChoice)
+}
+
+fn slots(view: &SeatView) -> Result<Slots, TarotError> {
+    let tracker = Tracker::new(view);
+    if tracker.taker.is_none() {
+        return Err(TarotError::NoAvailableChoice);
+    }
+    let allowed = tracker
+        .unseen()
+        .into_iter()
+        .map(|card| (card, tracker.candidates(&card)))
+        .collect();
+    Ok(Slots {
+        capacities: tracker.capacities,
+        allowed,
+    })
+}
+
+// Most constrained cards first, each one going to a slot with a chance matching its free space
+fn assign<R: Rng>(slots: &Slots, rng: &mut R) -> Option<Vec<Vec<Card>>> {
+    let mut capacities = slots.capacities.clone();
+    let mut cards = slots.allowed.clone();
+    cards.shuffle(rng);
+    cards.sort_by_key(|(_, seats)| seats.len());
+
+    let mut assigned = vec![Vec::new(); capacities.len()];
+    for (card, seats) in cards {
+        let free: Vec<usize> = seats.into_iter().filter(|s| capacities[*s] > 0).collect();
+        let slot = *free.choose_weighted(rng, |slot| capacities[*slot]).ok()?;
+        capacities[slot] -= 1;
+        assigned[slot].push(card);
+    }
+    Some(assigned)
+}
+
+fn record(view: &SeatView, mut assigned: Vec<Vec<Card>>) -> DealRecord {
+    let taker = view.taker.as_ref().map(|taker| taker.seat);
+    let pile = match assigned.pop() {
+        Some(pile) if !pile.is_empty() => pile,
+        _ => view.ecart.clone(),
+    };
+    assigned[view.seat] = view.hand.clone();
+
+    let played = played_cards(view);
+    let mut hands: Vec<Vec<Card>> = assigned
+        .into_iter()
+        .zip(played)
+        .map(|(hand, played)| [hand, played].concat())
+        .collect();
+    let mut record = DealRecord::new(view.players.clone());
+    match (&view.kitty, taker) {
+        (Some(kitty), Some(taker)) => {
+            hands[taker].extend_from_slice(&pile);
+            subtract(&mut hands[taker], kitty);
+            record.kitty = kitty.clone();
+            record.ecart = pile;
+        }
+        _ => record.kitty = pile,
+    }
+    record.hands = hands;
+    record.auction = view.auction.clone();
+    record.called_king = view.called_king;
+    record.handles = view
+        .handles
+        .iter()
+        .map(|handle| (handle.seat, handle.kind))
+        .collect();
+    // The current trick is kept even when empty, so that the declarations are replayed
+    record.tricks = view
+        .tricks
+        .iter()
+        .chain([&view.trick])
+        .map(|trick| RecordedTrick {
+            leader: trick.leader,
+            cards: trick.played_cards.clone(),
+        })
+        .collect();
+    record
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use crate::common::{
+    card::{Card, CardGetters},
+    cardset::{allowed_moves, winning_index, CardSet},
+    deal::Deal,
+    error::TarotError,
+    hand::Side,
+    phase::Phase,
+};
+
+const FOOL_EXCHANGE: f64 = 0.5;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Solution {
+    pub attack: f64,
+    pub defense: f64,
+    pub best: Option<Card>,
+}
+
+// Bounds of the points the attack still makes from the start of a trick
+#[derive(Debug, Copy, Clone)]
+struct Bounds {
+    lower: f64,
+    upper: f64,
+}
+
+// Hands and trick are kept as card sets and indexes, so moves and winners are bit operations
+#[derive(Debug, Clone)]
+struct Position {
+    hands: Vec<CardSet>,
+    sides: Vec<Side>,
+    leader: usize,
+    played: Vec<u32>,
+    current: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Solver {
+    table: HashMap<(u128, usize), Bounds>,
+    pub nodes: usize,
+}
+impl Solver {
+    // Open-hand alpha-beta search over the remaining tricks, the attack maximizing its points
+    pub fn solve(&mut self, deal: &Deal) -> Result<Solution, TarotError> {
+        if deal.phase != Phase::Play {
+            return Err(TarotError::InvalidPhase {
+                expected: Phase::Play,
+                found: deal.phase,
+            });
+        }
+        self.table.clear();
+        let mut position = Position {
+            hands: deal
+                .players
+                .iter()
+                .map(|p| CardSet::from(p.hand.cards.as_slice()))
+                .collect(),
+            sides: (0..deal.players.len())
+                .map(|seat| deal.side(seat))
+                .collect(),
+            leader: deal.trick.leader,
+            played: deal.trick.played_cards.iter().map(CardSet::index).collect(),
+            current: deal.current,
+        };
+        let total: f64 = position
+            .hands
+            .iter()
+            .flat_map(|hand| hand.indexes())
+            .chain(position.played.iter().copied())
+            .map(|index| CardSet::card(index).score())
+            .sum();
+
+        let is_attack = position.sides[position.current] == Side::Attack;
+        let mut best: Option<(u32, f64)> = None;
+        for card in moves(&position) {
+            play(&mut position, card);
+            let value = self.search(&mut position, f64::NEG_INFINITY, f64::INFINITY);
+            undo(&mut position, card);
+            let improves = best.is_none_or(|(_, best)| match is_attack {
+                true => value > best,
+                false => value < best,
+            });
+            if improves {
+                best = Some((card, value));
+            }
+        }
+        let (card, attack) = best.ok_or(TarotError::NoAvailableChoice)?;
+        Ok(Solution {
+            attack,
+            defense: total - attack,
+            best: Some(CardSet::card(card)),
+        })
+    }
+
+    fn search(&mut self, position: &mut Position, mut alpha: f64, mut beta: f64) -> f64 {
+        self.nodes += 1;
+        let n_players = position.hands.len();
+        if position.played.len() == n_players {
+            return self.end_trick(position, alpha, beta);
+        }
+
+        let key = position
+            .played
+            .is_empty()
+            .then(|| (mask(&position.hands), position.leader));
+        if let Some(bounds) = key.and_then(|key| self.table.get(&key)) {
+            if bounds.lower >= beta || bounds.lower == bounds.upper {
+                return bounds.lower;
+            }
+            if bounds.upper <= alpha {
+                return bounds.upper;
+            }
+            alpha = alpha.max(bounds.lower);
+            beta = beta.min(bounds.upper);
+        }
+        let (alpha_start, beta_start) = (alpha, beta);
+
+        let is_attack = position.sides[position.current] == Side::Attack;
+        let mut best = if is_attack {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+        for card in moves(position) {
+            play(position, card);
+            let value = self.search(position, alpha, beta);
+            undo(position, card);
+            if is_attack {
+                best = best.max(value);
+                alpha = alpha.max(value);
+            } else {
+                best = best.min(value);
+                beta = beta.min(value);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        if let Some(key) = key {
+            let bounds = self.table.entry(key).or_insert(Bounds {
+                lower: f64::NEG_INFINITY,
+                upper: f64::INFINITY,
+            });
+            if best <= alpha_start {
+                bounds.upper = bounds.upper.min(best);
+            } else if best >= beta_start {
+                bounds.lower = bounds.lower.max(best);
+            } else {
+                *bounds = Bounds {
+                    lower: best,
+                    upper: best,
+                };
+            }
+        }
+        best
+    }
+
+    fn end_trick(&mut self, position: &mut Position, alpha: f64, beta: f64) -> f64 {
+        let n_players = position.hands.len();
+        let is_last = position.hands.iter().all(|hand| hand.is_empty());
+        let winner = winning_index(&position.played).map_or(position.leader, |index| {
+            (position.leader + index) % n_players
+        });
+        let mut points = 0.0;
+        for (index, card) in position.played.iter().enumerate() {
+            let card = CardSet::card(*card);
+            let seat = (position.leader + index) % n_players;
+            points += match card.is_fool() && !is_last {
+                // The owner keeps the Fool and gives a low card instead
+                true if position.sides[seat] != position.sides[winner] => {
+                    match position.sides[seat] {
+                        Side::Attack => card.score() - FOOL_EXCHANGE,
+                        Side::Defense => FOOL_EXCHANGE,
+                    }
+                }
+                true => attack_share(position.sides[seat], card.score()),
+                false => attack_share(position.sides[winner], card.score()),
+            };
+        }
+        if is_last {
+            return points;
+        }
+
+        let played = std::mem::take(&mut position.played);
+        let leader = std::mem::replace(&mut position.leader, winner);
+        let current = std::mem::replace(&mut position.current, winner);
+        let rest = self.search(position, alpha - points, beta - points);
+        position.played = played;
+        position.leader = leader;
+        position.current = current;
+        points + rest
+    }
+}
+
+pub fn solve(deal: &Deal) -> Result<Solution, TarotError> {
+    Solver::default().solve(deal)
+}
+
+fn attack_share(side: Side, points: f64) -> f64 {
+    match side {
+        Side::Attack => points,
+        Side::Defense => 0.0,
+    }
+}
+
+// Strongest cards first to cut the search early
+fn moves(position: &Position) -> Vec<u32> {
+    let mut indexes: Vec<u32> = allowed_moves(position.hands[position.current], &position.played)
+        .indexes()
+        .collect();
+    indexes.sort_unstable_by_key(|index| std::cmp::Reverse(CardSet::card(*index).rank));
+    indexes
+}
+
+fn play(position: &mut Position, index: u32) {
+    position.hands[position.current].0 &= !(1 << index);
+    position.played.push(index);
+    position.current = (position.current + 1) % position.hands.len();
+}
+
+fn undo(position: &mut Position, index: u32) {
+    let n_players = position.hands.len();
+    position.current = (position.current + n_players - 1) % n_players;
+    position.played.pop();
+    position.hands[position.current].0 |= 1 << index;
+}
+
+// The remaining cards are enough to know who holds what, the hands being fixed from the start
+fn mask(hands: &[CardSet]) -> u128 {
+    hands.iter().fold(CardSet::EMPTY, |all, hand| all | *hand).0
+}
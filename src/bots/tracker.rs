@@ -0,0 +1,247 @@
+use crate::common::{
+    bid::Bids,
+    card::{Card, CardGetters, CardSuits, CardSuitsGetters, KING_RANK},
+    game::{new_deck, TOTAL_CARDS},
+    kitty::Kitty,
+    trick::{Trick, TrickGetters},
+    utils::subtract,
+    view::SeatView,
+};
+
+const NUMBER_TRUMPS: usize = 21;
+
+// What the played cards tell about the hand of a seat
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Constraint {
+    pub voids: Vec<CardSuits>,
+    pub max_trump: Option<u8>,
+}
+impl Constraint {
+    pub fn allows(&self, card: &Card) -> bool {
+        if card.is_fool() {
+            return true;
+        }
+        let above_max_trump =
+            card.suit.is_trump() && self.max_trump.is_some_and(|max| card.rank > max);
+        !self.voids.contains(&card.suit.name) && !above_max_trump
+    }
+}
+
+// Everything a seat can deduce from its view; the last slot stands for the kitty or the ecart
+#[derive(Debug, Clone)]
+pub struct Tracker {
+    pub seat: usize,
+    pub taker: Option<usize>,
+    pub partner: Option<usize>,
+    pub called_king_seen: bool,
+    pub played: Vec<Vec<Card>>,
+    pub constraints: Vec<Constraint>,
+    pub capacities: Vec<usize>,
+    hand: Vec<Card>,
+    candidates: Vec<(Card, Vec<usize>)>,
+}
+impl Tracker {
+    pub fn new(view: &SeatView) -> Self {
+        let n_players = view.n_players();
+        let taker = view.taker.as_ref().map(|taker| taker.seat);
+        let played = played_cards(view);
+        let kitty_size = Kitty::new(n_players).max_size;
+        let hand_size = (TOTAL_CARDS - kitty_size) / n_players;
+        let revealed = matches!(view.contract(), Bids::Petite | Bids::Garde);
+
+        let mut capacities: Vec<usize> = played
+            .iter()
+            .map(|cards| hand_size.saturating_sub(cards.len()))
+            .collect();
+        capacities[view.seat] = 0;
+        let pile_is_known = revealed && taker == Some(view.seat);
+        capacities.push(if pile_is_known { 0 } else { kitty_size });
+
+        let mut tracker = Tracker {
+            seat: view.seat,
+            taker,
+            partner: None,
+            called_king_seen: false,
+            constraints: constraints(view),
+            capacities,
+            hand: view.hand.clone(),
+            candidates: Vec::new(),
+            played,
+        };
+        tracker.follow_called_king(view);
+        tracker.candidates = tracker.compute_candidates(view, revealed);
+        tracker
+    }
+
+    pub fn pile(&self) -> usize {
+        self.capacities.len() - 1
+    }
+
+    pub fn played_cards(&self) -> Vec<Card> {
+        self.played.concat()
+    }
+
+    pub fn is_played(&self, card: &Card) -> bool {
+        self.played.iter().any(|cards| cards.contains(card))
+    }
+
+    pub fn is_void(&self, seat: usize, suit: CardSuits) -> bool {
+        self.constraints
+            .get(seat)
+            .is_some_and(|constraint| constraint.voids.contains(&suit))
+    }
+
+    // The Fool is not counted, it never wins a trick
+    pub fn remaining_trumps(&self) -> usize {
+        let played = self
+            .played_cards()
+            .iter()
+            .filter(|card| card.suit.is_trump() && !card.is_fool())
+            .count();
+        NUMBER_TRUMPS - played
+    }
+
+    pub fn unseen_trumps(&self) -> usize {
+        self.candidates
+            .iter()
+            .filter(|(card, _)| card.suit.is_trump() && !card.is_fool())
+            .count()
+    }
+
+    pub fn unseen(&self) -> Vec<Card> {
+        self.candidates.iter().map(|(card, _)| *card).collect()
+    }
+
+    // Slots where an unseen card may be, empty for the cards already known
+    pub fn candidates(&self, card: &Card) -> Vec<usize> {
+        self.candidates
+            .iter()
+            .find(|(unseen, _)| unseen == card)
+            .map(|(_, slots)| slots.clone())
+            .unwrap_or_default()
+    }
+
+    // Every candidate slot gets a share matching the cards it still misses
+    pub fn probability(&self, seat: usize, card: &Card) -> f64 {
+        if self.hand.contains(card) {
+            return if seat == self.seat { 1.0 } else { 0.0 };
+        }
+        let slots = self.candidates(card);
+        if !slots.contains(&seat) {
+            return 0.0;
+        }
+        let total: usize = slots.iter().map(|slot| self.capacities[*slot]).sum();
+        self.capacities[seat] as f64 / total as f64
+    }
+
+    // No unseen card of the same suit can beat it
+    pub fn is_master(&self, card: &Card) -> bool {
+        !card.is_fool()
+            && self.unseen().iter().all(|unseen| {
+                unseen.suit.name != card.suit.name || unseen.is_fool() || unseen.rank < card.rank
+            })
+    }
+
+    fn follow_called_king(&mut self, view: &SeatView) {
+        let Some(king) = view.called_king else {
+            return;
+        };
+        let kitty = view.kitty.clone().unwrap_or_default();
+        if self.hand.contains(&king) && self.taker != Some(self.seat) {
+            self.partner = Some(self.seat);
+        }
+        if let Some(seat) = self.played.iter().position(|cards| cards.contains(&king)) {
+            self.called_king_seen = true;
+            self.partner = Some(seat).filter(|seat| Some(*seat) != self.taker);
+        } else if kitty.contains(&king) {
+            self.called_king_seen = true;
+        }
+    }
+
+    fn compute_candidates(&self, view: &SeatView, revealed: bool) -> Vec<(Card, Vec<usize>)> {
+        let pile = self.pile();
+        let mut unseen = new_deck();
+        subtract(&mut unseen, &view.hand);
+        subtract(&mut unseen, &view.ecart);
+        subtract(&mut unseen, &self.played_cards());
+
+        let revealed_kitty = view.kitty.clone().unwrap_or_default();
+        // A queen is only called by a taker holding the four kings
+        let taker_has_kings = view.called_king.is_some_and(|card| card.rank != KING_RANK);
+        unseen
+            .into_iter()
+            .map(|card| {
+                let handle = view.handles.iter().find(|h| h.cards.contains(&card));
+                let slots = (0..=pile)
+                    .filter(|slot| self.capacities[*slot] > 0)
+                    .filter(|slot| *slot == pile || self.constraints[*slot].allows(&card))
+                    .filter(|slot| *slot != pile || !revealed || is_discardable(&card))
+                    .filter(|slot| {
+                        !revealed_kitty.contains(&card)
+                            || Some(*slot) == self.taker
+                            || *slot == pile
+                    })
+                    .filter(|slot| !taker_has_kings || !is_king(&card) || Some(*slot) == self.taker)
+                    .filter(|slot| handle.is_none_or(|handle| *slot == handle.seat))
+                    .collect();
+                (card, slots)
+            })
+            .collect()
+    }
+}
+
+pub fn played_cards(view: &SeatView) -> Vec<Vec<Card>> {
+    let mut played = vec![Vec::new(); view.n_players()];
+    for trick in view.tricks.iter().chain([&view.trick]) {
+        for (index, card) in trick.played_cards.iter().enumerate() {
+            played[(trick.leader + index) % view.n_players()].push(*card);
+        }
+    }
+    played
+}
+
+fn constraints(view: &SeatView) -> Vec<Constraint> {
+    let mut constraints = vec![Constraint::default(); view.n_players()];
+    for trick in view.tricks.iter().chain([&view.trick]) {
+        for (index, card) in trick.played_cards.iter().enumerate() {
+            let before = Trick {
+                played_cards: trick.played_cards[..index].to_vec(),
+                leader: trick.leader,
+            };
+            let (Some(played_suit), false) = (before.played_suit(), card.is_fool()) else {
+                continue;
+            };
+            let constraint = &mut constraints[(trick.leader + index) % view.n_players()];
+            if card.suit.name != played_suit {
+                constraint.voids.push(played_suit);
+                if !card.suit.is_trump() {
+                    constraint.voids.push(CardSuits::Trumps);
+                }
+            }
+            if !card.suit.is_trump() {
+                continue;
+            }
+            // A trump under the best played one means there was no higher trump to play
+            let best_trump = before
+                .played_cards
+                .iter()
+                .filter(|card| card.suit.is_trump() && !card.is_fool())
+                .map(|card| card.rank)
+                .max();
+            if let Some(best_trump) = best_trump.filter(|best| card.rank < *best) {
+                let max_trump = constraint.max_trump.get_or_insert(best_trump);
+                *max_trump = (*max_trump).min(best_trump);
+            }
+        }
+    }
+    constraints
+}
+
+fn is_king(card: &Card) -> bool {
+    !card.suit.is_trump() && card.rank == KING_RANK
+}
+
+// Trumps are only discarded when nothing else is left, which never happens in practice
+fn is_discardable(card: &Card) -> bool {
+    !card.suit.is_trump() && !is_king(card)
+}
@@ -0,0 +1,265 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::bots::{
+    difficulty::Difficulties,
+    engine::Engine,
+    ismcts::{Budget, SearchConfig},
+};
+use crate::common::{
+    arena::{ArenaConfig, DEFAULT_BOARDS},
+    error::TarotError,
+    locale::Languages,
+    player::Player,
+    profile::default_profiles,
+    simulation::{SimulationConfig, DEFAULT_DEALS, DEFAULT_PLAYERS},
+};
+use crate::net::server::{DEFAULT_ADDRESS, DEFAULT_GRACE};
+
+pub const USAGE: &str =
+    "Usage: tarot-cli [tui | simulate | arena | serve | join | lobby | http | stats] [--players <3|4|5>]
+                 [--seat <player>=<human|beginner|intermediate|expert|engine:<command>>]...
+                 [--iterations <n> | --think-time <ms>] [--seed <n>]
+                 [--deals <n>] [--threads <n>] [--deal-seed <n>] [--strategies <level>,<level>...]
+                 [--analysis] [--no-color] [--lang <en|fr>]
+                 [--address <host:port>] [--remote <n>] [--name <name>]
+                 [--grace <seconds>] [--token <token>] [--profiles <path>]";
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum Commands {
+    #[default]
+    Play,
+    Tui,
+    Simulate,
+    Arena,
+    Serve,
+    Join,
+    Lobby,
+    Http,
+    Stats,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Seats {
+    Human,
+    Bot(Difficulties),
+    Engine(String),
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Options {
+    pub command: Commands,
+    pub players: Option<u8>,
+    pub deals: Option<usize>,
+    pub threads: Option<usize>,
+    pub deal_seed: Option<u64>,
+    pub strategies: Vec<Difficulties>,
+    pub analysis: bool,
+    pub no_color: bool,
+    pub language: Option<Languages>,
+    pub address: Option<String>,
+    pub remote: Option<usize>,
+    pub name: Option<String>,
+    pub grace: Option<u64>,
+    pub token: Option<String>,
+    pub profiles: Option<PathBuf>,
+    pub seats: Vec<(u8, Seats)>,
+    pub search: SearchConfig,
+}
+impl Options {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, TarotError> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "simulate" if options == Options::default() => options.command = Commands::Simulate,
+                "arena" if options == Options::default() => options.command = Commands::Arena,
+                "tui" if options == Options::default() => options.command = Commands::Tui,
+                "serve" if options == Options::default() => options.command = Commands::Serve,
+                "join" if options == Options::default() => options.command = Commands::Join,
+                "lobby" if options == Options::default() => options.command = Commands::Lobby,
+                "http" if options == Options::default() => options.command = Commands::Http,
+                "stats" if options == Options::default() => options.command = Commands::Stats,
+                "--address" => options.address = Some(parse_value(&arg, args.next())?),
+                "--remote" => options.remote = Some(parse_value(&arg, args.next())?),
+                "--name" => options.name = Some(parse_value(&arg, args.next())?),
+                "--grace" => options.grace = Some(parse_value(&arg, args.next())?),
+                "--token" => options.token = Some(parse_value(&arg, args.next())?),
+                "--profiles" => options.profiles = Some(parse_value(&arg, args.next())?),
+                "--strategies" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| invalid("--strategies needs a value"))?;
+                    options.strategies = value
+                        .split(',')
+                        .map(|name| name.parse().map_err(|error: String| invalid(&error)))
+                        .collect::<Result<_, _>>()?;
+                }
+                "--players" => {
+                    let players = parse_value(&arg, args.next())?;
+                    if !(3..=5).contains(&players) {
+                        return Err(invalid("Tarot is played with 3 to 5 players"));
+                    }
+                    options.players = Some(players);
+                }
+                "--analysis" => options.analysis = true,
+                "--no-color" => options.no_color = true,
+                "--lang" => options.language = Some(parse_value(&arg, args.next())?),
+                "--deals" => options.deals = Some(parse_value(&arg, args.next())?),
+                "--threads" => options.threads = Some(parse_value(&arg, args.next())?),
+                "--deal-seed" => options.deal_seed = Some(parse_value(&arg, args.next())?),
+                "--seat" => {
+                    let value = args.next().ok_or_else(|| invalid("--seat needs a value"))?;
+                    options.seats.push(parse_seat(&value)?);
+                }
+                "--iterations" => {
+                    options.search.budget = Budget::Iterations(parse_value(&arg, args.next())?)
+                }
+                "--think-time" => {
+                    let millis = parse_value(&arg, args.next())?;
+                    options.search.budget = Budget::Time(Duration::from_millis(millis));
+                }
+                "--seed" => options.search.seed = Some(parse_value(&arg, args.next())?),
+                _ => return Err(invalid(&format!("Unknown argument '{}'", arg))),
+            }
+        }
+        Ok(options)
+    }
+
+    pub fn n_players(&self) -> u8 {
+        self.players.unwrap_or(DEFAULT_PLAYERS)
+    }
+
+    // Only bots sit at a simulated table
+    pub fn simulation(&self) -> Result<SimulationConfig, TarotError> {
+        let mut levels = vec![Default::default(); self.n_players() as usize];
+        for (id, seat) in &self.seats {
+            let level = levels
+                .get_mut((*id as usize).wrapping_sub(1))
+                .ok_or_else(|| invalid(&format!("There is no player {}", id)))?;
+            match seat {
+                Seats::Human => return Err(invalid("A simulation cannot seat a human")),
+                Seats::Bot(difficulty) => *level = *difficulty,
+                Seats::Engine(_) => return Err(invalid("A simulation only seats built-in bots")),
+            }
+        }
+        Ok(SimulationConfig {
+            deals: self.deals.unwrap_or(DEFAULT_DEALS),
+            threads: self.threads.unwrap_or(1),
+            n_players: self.n_players(),
+            levels,
+            seed: self.deal_seed,
+            search: self.search,
+        })
+    }
+
+    // For the arena, the number of deals is the number of boards played in every seating
+    pub fn arena(&self) -> Result<ArenaConfig, TarotError> {
+        let default = ArenaConfig::default();
+        let strategies = match self.strategies.is_empty() {
+            true => default.strategies,
+            false => self.strategies.clone(),
+        };
+        if strategies.len() < 2 {
+            return Err(invalid("The arena needs at least two strategies"));
+        }
+        let config = ArenaConfig {
+            boards: self.deals.unwrap_or(DEFAULT_BOARDS),
+            threads: self.threads.unwrap_or(1),
+            n_players: self.n_players(),
+            strategies,
+            seed: self.deal_seed,
+            search: self.search,
+        };
+        if !config.is_balanced() {
+            return Err(invalid(
+                "The strategies must share the seats evenly: use as many strategies as players, or a divisor of the players",
+            ));
+        }
+        Ok(config)
+    }
+
+    pub fn address(&self) -> String {
+        self.address
+            .clone()
+            .unwrap_or_else(|| String::from(DEFAULT_ADDRESS))
+    }
+
+    pub fn profiles(&self) -> PathBuf {
+        self.profiles.clone().unwrap_or_else(default_profiles)
+    }
+
+    // How long the seat of a dropped client waits for it, a bot plays meanwhile
+    pub fn grace(&self) -> Duration {
+        self.grace.map_or(DEFAULT_GRACE, Duration::from_secs)
+    }
+
+    // The table needs at least one remote player, the other seats are bots
+    pub fn remote(&self) -> Result<usize, TarotError> {
+        let remote = self.remote.unwrap_or(1);
+        if !(1..=self.n_players() as usize).contains(&remote) {
+            return Err(invalid("The number of remote players must fit the table"));
+        }
+        Ok(remote)
+    }
+
+    pub fn apply(&self, players: &mut [Player]) -> Result<(), TarotError> {
+        for player in players.iter_mut() {
+            player.search = self.search;
+        }
+        // The name of the local player is the one of its profile
+        if let (Some(name), Some(human)) = (&self.name, players.iter_mut().find(|p| p.is_human)) {
+            human.name = name.clone();
+        }
+        for (id, seat) in &self.seats {
+            let player = players
+                .iter_mut()
+                .find(|player| player.id == *id)
+                .ok_or_else(|| invalid(&format!("There is no player {}", id)))?;
+            match seat {
+                Seats::Human => player.is_human = true,
+                Seats::Bot(level) => {
+                    player.is_human = false;
+                    player.level = *level;
+                }
+                Seats::Engine(command) => {
+                    player.is_human = false;
+                    let engine = Engine::launch(command)?;
+                    player.name = engine.name.clone();
+                    player.engine = Some(Arc::new(Mutex::new(engine)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_value<T: FromStr>(arg: &str, value: Option<String>) -> Result<T, TarotError> {
+    let value = value.ok_or_else(|| invalid(&format!("{} needs a value", arg)))?;
+    value
+        .parse()
+        .map_err(|_| invalid(&format!("Invalid value '{}' for {}", value, arg)))
+}
+
+fn parse_seat(value: &str) -> Result<(u8, Seats), TarotError> {
+    let (id, kind) = value
+        .split_once('=')
+        .ok_or_else(|| invalid(&format!("Expected <player>=<kind>, found '{}'", value)))?;
+    let id = id
+        .parse::<u8>()
+        .map_err(|_| invalid(&format!("Invalid player '{}'", id)))?;
+    let seat = match kind {
+        "human" => Seats::Human,
+        _ => match kind.strip_prefix("engine:") {
+            Some(command) => Seats::Engine(command.to_string()),
+            None => Seats::Bot(kind.parse().map_err(|error: String| invalid(&error))?),
+        },
+    };
+    Ok((id, seat))
+}
+
+fn invalid(message: &str) -> TarotError {
+    TarotError::InvalidConfiguration(format!("{}\n{}", message, USAGE))
+}
@@ -0,0 +1,90 @@
+use crate::common::{
+    event::{Event, Observer},
+    locale::{fill, text, Messages},
+    render::{color, paint, paint_all},
+};
+
+#[derive(Debug, Default)]
+pub struct LineRenderer {
+    players: Vec<String>,
+}
+impl LineRenderer {
+    fn name(&self, seat: usize) -> &str {
+        self.players.get(seat).map_or("?", |name| name.as_str())
+    }
+}
+
+impl Observer for LineRenderer {
+    fn notify(&mut self, event: &Event) {
+        match event {
+            Event::DealerChosen { name } => println!("{}", fill(Messages::DealerIs, &[name])),
+            Event::DealStarted { players } => self.players = players.clone(),
+            Event::BidMade { seat, bid } => {
+                println!("{}", fill(Messages::BidMade, &[&self.name(*seat), bid]))
+            }
+            Event::KingCalled { seat, king } => {
+                let king = paint(king, color());
+                println!(
+                    "\n{}",
+                    fill(Messages::KingCalled, &[&self.name(*seat), &king])
+                )
+            }
+            Event::KittyRevealed { cards } => {
+                let cards = paint_all(cards, color());
+                println!("\n\n{}", fill(Messages::KittyContains, &[&cards]));
+            }
+            Event::HandleDeclared { handle } => {
+                let cards = paint_all(&handle.cards, color());
+                println!(
+                    "\n{}",
+                    fill(
+                        Messages::HandleShown,
+                        &[&self.name(handle.seat), &handle.kind.label(), &cards]
+                    )
+                );
+            }
+            Event::CardPlayed { seat, card } => {
+                let card = paint(card, color());
+                println!(
+                    "{}",
+                    fill(Messages::CardPlayed, &[&self.name(*seat), &card])
+                )
+            }
+            Event::TrickWon { seat, cards } => {
+                let cards = paint_all(cards, color());
+                println!(
+                    "{}\n",
+                    fill(Messages::TrickWon, &[&self.name(*seat), &cards])
+                )
+            }
+            Event::DealScored { score } => {
+                let result = text(match score.contract_won {
+                    true => Messages::Won,
+                    false => Messages::Lost,
+                });
+                println!(
+                    "\n{}",
+                    fill(
+                        Messages::DealResult,
+                        &[
+                            &score.taker_points,
+                            &score.oudlers,
+                            &score.needed_points,
+                            &score.bid,
+                            &result
+                        ]
+                    )
+                );
+                if let Some(side) = score.petit_au_bout {
+                    println!("{}", fill(Messages::PetitAuBout, &[&side.label()]));
+                }
+                for (seat, points) in score.scores.iter().enumerate() {
+                    println!(
+                        "{}",
+                        fill(Messages::PlayerScore, &[&self.name(seat), points])
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::fmt::{Display, Formatter, Result};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    bid::Bids,
+    card::Card,
+    handle::Handles,
+    locale::{fill, text, Messages},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    Bid(Bids),
+    CallKing(Card),
+    Discard(Card),
+    Declare(Option<Handles>),
+    Play(Card),
+}
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Bid(bid) => write!(f, "{}", fill(Messages::ActionBid, &[bid])),
+            Self::CallKing(king) => write!(f, "{}", fill(Messages::ActionCall, &[king])),
+            Self::Discard(card) => write!(f, "{}", fill(Messages::ActionDiscard, &[card])),
+            Self::Declare(Some(handle)) => {
+                write!(f, "{}", fill(Messages::ActionDeclare, &[&handle.label()]))
+            }
+            Self::Declare(None) => write!(f, "{}", text(Messages::ActionDeclareNothing)),
+            Self::Play(card) => write!(f, "{}", fill(Messages::ActionPlay, &[card])),
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::fmt::{Display, Formatter, Result};
+use std::thread;
+
+use crate::bots::{difficulty::Difficulties, ismcts::SearchConfig};
+
+use super::simulation::{
+    play_deal, silence_deal_panics, Outcome, SimulationConfig, DEFAULT_PLAYERS,
+};
+
+pub const DEFAULT_BOARDS: usize = 250;
+const INITIAL_RATING: f64 = 1500.0;
+const RATING_FACTOR: f64 = 16.0;
+const Z_95: f64 = 1.96;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaConfig {
+    pub boards: usize,
+    pub threads: usize,
+    pub n_players: u8,
+    pub strategies: Vec<Difficulties>,
+    // Seed of the first board
+    pub seed: Option<u64>,
+    pub search: SearchConfig,
+}
+impl Default for ArenaConfig {
+    fn default() -> Self {
+        ArenaConfig {
+            boards: DEFAULT_BOARDS,
+            threads: 1,
+            n_players: DEFAULT_PLAYERS,
+            strategies: vec![Difficulties::Beginner, Difficulties::Intermediate],
+            seed: None,
+            search: SearchConfig::default(),
+        }
+    }
+}
+impl ArenaConfig {
+    // A strategy holds as many seats as every other strategy at the table, so the mean of
+    // its seats gives every seat the same weight. Fewer strategies than players must divide
+    // the players, more strategies than players sit one per seat
+    pub fn is_balanced(&self) -> bool {
+        let (n_strategies, n_players) = (self.strategies.len(), self.n_players as usize);
+        n_strategies > 0 && (n_strategies >= n_players || n_players % n_strategies == 0)
+    }
+
+    // Every strategy sits once at every seat over the seatings of a board
+    pub fn seating(&self, rotation: usize) -> Vec<usize> {
+        let n_strategies = self.strategies.len().max(1);
+        (0..self.n_players as usize)
+            .map(|seat| (seat + rotation) % n_strategies)
+            .collect()
+    }
+
+    pub fn n_seatings(&self) -> usize {
+        self.strategies.len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub strategy: Difficulties,
+    pub mean: f64,
+    // Half width of the 95% confidence interval of the mean
+    pub margin: f64,
+    pub rating: f64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ArenaReport {
+    pub seed: u64,
+    pub boards: usize,
+    pub seatings: usize,
+    pub standings: Vec<Standing>,
+    pub failures: Vec<(u64, String)>,
+}
+impl Display for ArenaReport {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        writeln!(
+            f,
+            "Played {} boards in {} seatings ({} deals) with seed {}",
+            self.boards,
+            self.seatings,
+            self.boards * self.seatings,
+            self.seed
+        )?;
+        writeln!(
+            f,
+            "\n{:<14}{:>12}{:>12}{:>8}",
+            "Strategy", "Mean score", "95% CI", "Elo"
+        )?;
+        for standing in &self.standings {
+            writeln!(
+                f,
+                "{:<14}{:>12.2}{:>12}{:>8.0}",
+                standing.strategy.to_string(),
+                standing.mean,
+                format!("± {:.2}", standing.margin),
+                standing.rating
+            )?;
+        }
+        writeln!(f, "\nFailures: {}", self.failures.len())?;
+        for (seed, reason) in &self.failures {
+            writeln!(f, "Deal with seed {}: {}", seed, reason)?;
+        }
+        Ok(())
+    }
+}
+
+// Mean score of every strategy at one seating, None for the strategies not at the table
+type Seating = Option<Vec<Option<f64>>>;
+type Board = (usize, Vec<Seating>, Vec<(u64, String)>);
+
+// The same cards are dealt to every seating of a board, so luck cancels out between strategies
+pub fn arena(config: &ArenaConfig) -> ArenaReport {
+    silence_deal_panics();
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let threads = config.threads.clamp(1, config.boards.max(1));
+    let mut boards: Vec<Board> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| {
+                scope.spawn(move || {
+                    (worker..config.boards)
+                        .step_by(threads)
+                        .map(|board| {
+                            let (seatings, failures) =
+                                play_board(config, seed.wrapping_add(board as u64));
+                            (board, seatings, failures)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_default())
+            .collect()
+    });
+    boards.sort_by_key(|(board, _, _)| *board);
+
+    let n_strategies = config.strategies.len();
+    let mut samples = vec![Vec::new(); n_strategies];
+    let mut ratings = vec![INITIAL_RATING; n_strategies];
+    for (_, seatings, _) in &boards {
+        for (strategy, samples) in samples.iter_mut().enumerate() {
+            let scores: Vec<f64> = seatings
+                .iter()
+                .flatten()
+                .filter_map(|scores| scores[strategy])
+                .collect();
+            if !scores.is_empty() {
+                samples.push(scores.iter().sum::<f64>() / scores.len() as f64);
+            }
+        }
+        for scores in seatings.iter().flatten() {
+            update_ratings(&mut ratings, scores);
+        }
+    }
+
+    let mut standings: Vec<Standing> = config
+        .strategies
+        .iter()
+        .zip(samples.iter().zip(ratings))
+        .map(|(strategy, (samples, rating))| {
+            let (mean, margin) = mean_and_margin(samples);
+            Standing {
+                strategy: *strategy,
+                mean,
+                margin,
+                rating,
+            }
+        })
+        .collect();
+    standings.sort_by(|a, b| b.mean.total_cmp(&a.mean));
+
+    ArenaReport {
+        seed,
+        boards: config.boards,
+        seatings: config.n_seatings(),
+        standings,
+        failures: boards
+            .into_iter()
+            .flat_map(|(_, _, failures)| failures)
+            .collect(),
+    }
+}
+
+fn play_board(config: &ArenaConfig, seed: u64) -> (Vec<Seating>, Vec<(u64, String)>) {
+    let mut failures = Vec::new();
+    let seatings = (0..config.n_seatings())
+        .map(|rotation| {
+            let seating = config.seating(rotation);
+            let table = SimulationConfig {
+                deals: 1,
+                threads: 1,
+                n_players: config.n_players,
+                levels: seating
+                    .iter()
+                    .map(|index| config.strategies[*index])
+                    .collect(),
+                seed: Some(seed),
+                search: config.search,
+            };
+            let scores = match play_deal(&table, seed) {
+                Outcome::Scored(score) => score.scores,
+                Outcome::Passed => vec![0.0; seating.len()],
+                Outcome::Failed(reason) => {
+                    failures.push((seed, reason));
+                    return None;
+                }
+            };
+            let means = (0..config.strategies.len())
+                .map(|strategy| {
+                    let seats: Vec<f64> = seating
+                        .iter()
+                        .zip(&scores)
+                        .filter(|(index, _)| **index == strategy)
+                        .map(|(_, score)| *score)
+                        .collect();
+                    (!seats.is_empty()).then(|| seats.iter().sum::<f64>() / seats.len() as f64)
+                })
+                .collect();
+            Some(means)
+        })
+        .collect();
+    (seatings, failures)
+}
+
+// Every pair of strategies at the table plays a game won by the best mean score
+fn update_ratings(ratings: &mut [f64], scores: &[Option<f64>]) {
+    if scores.iter().flatten().all(|score| *score == 0.0) {
+        return;
+    }
+    let before = ratings.to_vec();
+    for (a, score_a) in scores.iter().enumerate() {
+        for (b, score_b) in scores.iter().enumerate().skip(a + 1) {
+            let (Some(score_a), Some(score_b)) = (score_a, score_b) else {
+                continue;
+            };
+            let result = match score_a.total_cmp(score_b) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Less => 0.0,
+            };
+            let expected = 1.0 / (1.0 + 10f64.powf((before[b] - before[a]) / 400.0));
+            ratings[a] += RATING_FACTOR * (result - expected);
+            ratings[b] -= RATING_FACTOR * (result - expected);
+        }
+    }
+}
+
+fn mean_and_margin(samples: &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    if samples.is_empty() {
+        return (0.0, 0.0);
+    }
+    let mean = samples.iter().sum::<f64>() / n;
+    if samples.len() == 1 {
+        return (mean, 0.0);
+    }
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, Z_95 * (variance / n).sqrt())
+}
@@ -1,12 +1,18 @@
 use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
 
 use super::{
     card::Card,
-    score::{compute_oudlers, compute_points},
-    utils::{compare, display, select},
+    error::TarotError,
+    evaluation::ideal_bid,
+    locale::{text, Messages},
+    render::print_hand,
+    utils::{compare, select_with_hint, HintSource, Lines},
 };
 
-#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Bids {
     Petite,
     Garde,
@@ -17,25 +23,51 @@ pub enum Bids {
 }
 impl Display for Bids {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{:?}", self)
+        let message = match self {
+            Self::Petite => Messages::Petite,
+            Self::Garde => Messages::Garde,
+            Self::GardeSans => Messages::GardeSans,
+            Self::GardeContre => Messages::GardeContre,
+            Self::Passe => Messages::Passe,
+        };
+        write!(f, "{}", text(message))
+    }
+}
+impl FromStr for Bids {
+    type Err = String;
+
+    fn from_str(name: &str) -> std::result::Result<Self, Self::Err> {
+        match name {
+            "Petite" => Ok(Self::Petite),
+            "Garde" => Ok(Self::Garde),
+            "GardeSans" => Ok(Self::GardeSans),
+            "GardeContre" => Ok(Self::GardeContre),
+            "Passe" => Ok(Self::Passe),
+            _ => Err(format!("Unknown bid '{}'", name)),
+        }
     }
 }
 impl Bids {
-    const AVAILABLE_BIDS: [Self; 4] = [
+    pub const AVAILABLE_BIDS: [Self; 4] = [
         Self::Petite,
         Self::Garde,
         Self::GardeSans,
         Self::GardeContre,
     ];
+
+    // The name parsed back by FromStr, whatever the language
+    pub fn id(&self) -> String {
+        format!("{:?}", self)
+    }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Bid {
     pub current: Bids,
 }
 impl Display for Bid {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{:?}", self.current)
+        write!(f, "{}", self.current)
     }
 }
 impl Bid {
@@ -51,15 +83,25 @@ impl Bid {
 
         available_bids
     }
-    pub fn human_choose(&mut self, cards: &[Card]) -> Bids {
-        println!("\nYour cards:");
-        display(cards);
+    pub fn human_choose(
+        &mut self,
+        cards: &[Card],
+        hint: Option<HintSource>,
+        input: Option<&Lines>,
+    ) -> std::result::Result<Bids, TarotError> {
+        println!("\n{}", text(Messages::YourCards));
+        print_hand(cards, &[]);
         let available_bids = self.get_available_bids();
-        self.current = select(Some("What is your bid?"), Some(available_bids)).unwrap();
-        self.current
+        self.current = select_with_hint(
+            Some(text(Messages::WhatIsYourBid)),
+            Some(available_bids),
+            hint,
+            input,
+        )?;
+        Ok(self.current)
     }
-    pub fn bot_choose(&mut self, cards: &[Card]) -> Bids {
-        let ideal_bid = taker_evaluation(cards);
+    pub fn bot_choose(&mut self, cards: &[Card], n_players: usize) -> Bids {
+        let ideal_bid = ideal_bid(cards, n_players);
         match ideal_bid {
             Bids::Passe => ideal_bid,
             _ => {
@@ -86,16 +128,3 @@ pub fn compare_bids(bid: &Bids, active_bid: &Bids) -> bool {
         }
     }
 }
-
-pub fn taker_evaluation(cards: &[Card]) -> Bids {
-    let n_oudlers = compute_oudlers(cards) as f64;
-    let hand_score = compute_points(cards) % 5.0;
-    let evaluation = n_oudlers * hand_score;
-    match evaluation {
-        0.0..2.0 => Bids::Passe,
-        2.0..4.0 => Bids::Petite,
-        4.0..6.0 => Bids::Garde,
-        6.0..8.0 => Bids::GardeSans,
-        _ => Bids::GardeContre,
-    }
-}
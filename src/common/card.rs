@@ -1,12 +1,17 @@
 use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::locale::{text, Messages};
 
 pub const KING_RANK: u8 = 14;
-const QUEEN_RANK: u8 = 13;
-const KNIGHT_RANK: u8 = 12;
-const JACK_RANK: u8 = 11;
-const LITTLE_RANK: u8 = 1;
-const BIG_RANK: u8 = 21;
-const FOOL_RANK: u8 = 22;
+pub const QUEEN_RANK: u8 = 13;
+pub const KNIGHT_RANK: u8 = 12;
+pub const JACK_RANK: u8 = 11;
+pub const LITTLE_RANK: u8 = 1;
+pub const BIG_RANK: u8 = 21;
+pub const FOOL_RANK: u8 = 22;
 
 pub trait CardSuitsGetters {
     fn is_trump(&self) -> bool;
@@ -33,6 +38,16 @@ impl CardSuits {
         Self::Spades,
         Self::Trumps,
     ];
+
+    pub fn label(&self) -> &'static str {
+        text(match self {
+            Self::Clubs => Messages::Clubs,
+            Self::Diamonds => Messages::Diamonds,
+            Self::Hearts => Messages::Hearts,
+            Self::Spades => Messages::Spades,
+            Self::Trumps => Messages::Trumps,
+        })
+    }
 }
 impl CardSuitsGetters for CardSuits {
     fn is_trump(&self) -> bool {
@@ -65,6 +80,8 @@ impl CardSuitsGetters for Suit {
 
 pub trait CardGetters {
     fn is_oudler(&self) -> bool;
+    fn is_fool(&self) -> bool;
+    fn is_petit(&self) -> bool;
     fn score(&self) -> f64;
     fn name(&self) -> String;
     fn id(&self) -> String;
@@ -74,7 +91,9 @@ pub trait CardActions {
     fn is_superior_than(&self, card: &Card, played_suit: Option<CardSuits>) -> bool;
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+// Exchanged as its id, like in the notation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
 pub struct Card {
     pub rank: u8,
     pub suit: Suit,
@@ -104,26 +123,16 @@ impl CardGetters for Card {
         }
     }
     fn name(&self) -> String {
-        match (self.rank, self.suit.name) {
-            (FOOL_RANK, CardSuits::Trumps) => String::from("Fool"),
-            (KING_RANK, CardSuits::Clubs)
-            | (KING_RANK, CardSuits::Spades)
-            | (KING_RANK, CardSuits::Hearts)
-            | (KING_RANK, CardSuits::Diamonds) => String::from("King"),
-            (QUEEN_RANK, CardSuits::Clubs)
-            | (QUEEN_RANK, CardSuits::Spades)
-            | (QUEEN_RANK, CardSuits::Hearts)
-            | (QUEEN_RANK, CardSuits::Diamonds) => String::from("Queen"),
-            (KNIGHT_RANK, CardSuits::Clubs)
-            | (KNIGHT_RANK, CardSuits::Spades)
-            | (KNIGHT_RANK, CardSuits::Hearts)
-            | (KNIGHT_RANK, CardSuits::Diamonds) => String::from("Knight"),
-            (JACK_RANK, CardSuits::Clubs)
-            | (JACK_RANK, CardSuits::Spades)
-            | (JACK_RANK, CardSuits::Hearts)
-            | (JACK_RANK, CardSuits::Diamonds) => String::from("Jack"),
-            (_, _) => self.rank.to_string(),
-        }
+        let message = match (self.rank, self.suit.name) {
+            (FOOL_RANK, CardSuits::Trumps) => Messages::Fool,
+            (_, CardSuits::Trumps) => return self.rank.to_string(),
+            (KING_RANK, _) => Messages::King,
+            (QUEEN_RANK, _) => Messages::Queen,
+            (KNIGHT_RANK, _) => Messages::Knight,
+            (JACK_RANK, _) => Messages::Jack,
+            (_, _) => return self.rank.to_string(),
+        };
+        String::from(text(message))
     }
     fn id(&self) -> String {
         format!("{}{}", self.suit.initial, self.rank)
@@ -134,29 +143,31 @@ impl CardGetters for Card {
             (_, _) => false,
         }
     }
+    fn is_fool(&self) -> bool {
+        self.rank == FOOL_RANK && self.suit.is_trump()
+    }
+    fn is_petit(&self) -> bool {
+        self.rank == LITTLE_RANK && self.suit.is_trump()
+    }
 }
 
 impl CardActions for Card {
     fn is_superior_than(&self, card: &Card, played_suit: Option<CardSuits>) -> bool {
         match played_suit {
             Some(played_suit) => match (self.suit.name, card.suit.name, played_suit) {
-                (CardSuits::Trumps, _, __) => {
+                (CardSuits::Trumps, _, _) => {
                     if card.suit.name.is_trump() {
                         self.rank > card.rank
                     } else {
                         true
                     }
                 }
-                (_, CardSuits::Trumps, __) => false,
+                (_, CardSuits::Trumps, _) => false,
                 _ => {
                     if self.suit.name == played_suit && card.suit.name == played_suit {
                         self.rank > card.rank
-                    } else if self.suit.name == played_suit && card.suit.name != played_suit {
-                        true
-                    } else if self.suit.name != played_suit && card.suit.name == played_suit {
-                        false
                     } else {
-                        false
+                        self.suit.name == played_suit && card.suit.name != played_suit
                     }
                 }
             },
@@ -175,6 +186,43 @@ impl CardActions for Card {
     }
 }
 
+impl From<Card> for String {
+    fn from(card: Card) -> Self {
+        card.id()
+    }
+}
+impl TryFrom<String> for Card {
+    type Error = String;
+
+    fn try_from(id: String) -> std::result::Result<Self, Self::Error> {
+        id.parse()
+    }
+}
+
+impl FromStr for Card {
+    type Err = String;
+
+    fn from_str(id: &str) -> std::result::Result<Self, Self::Err> {
+        let mut chars = id.chars();
+        let suit = match chars.next() {
+            Some('C') => CardSuits::Clubs,
+            Some('D') => CardSuits::Diamonds,
+            Some('H') => CardSuits::Hearts,
+            Some('S') => CardSuits::Spades,
+            Some('T') => CardSuits::Trumps,
+            _ => return Err(format!("Unknown suit in card id '{}'", id)),
+        };
+        let max_rank = match suit {
+            CardSuits::Trumps => FOOL_RANK,
+            _ => KING_RANK,
+        };
+        match chars.as_str().parse::<u8>() {
+            Ok(rank) if (1..=max_rank).contains(&rank) => Ok(Card::new(rank, suit)),
+            _ => Err(format!("Invalid rank in card id '{}'", id)),
+        }
+    }
+}
+
 fn get_suit_data(name: CardSuits) -> (char, char) {
     match name {
         CardSuits::Clubs => ('♣', 'C'),
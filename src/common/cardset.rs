@@ -0,0 +1,196 @@
+use std::fmt::{Display, Formatter, Result};
+use std::ops::{BitAnd, BitOr, Not, Sub};
+
+use super::card::{Card, CardSuits};
+
+const SUIT_SIZE: u32 = 14;
+const TRUMP_OFFSET: u32 = 4 * SUIT_SIZE;
+const FOOL_INDEX: u32 = TRUMP_OFFSET + 21;
+const SIZE: u32 = FOOL_INDEX + 1;
+
+// One bit per card: the four suits from the 1 to the King, then the trumps from the Petit to the Fool
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CardSet(pub u128);
+impl CardSet {
+    pub const EMPTY: Self = CardSet(0);
+    pub const FULL: Self = CardSet((1 << SIZE) - 1);
+    pub const FOOL: Self = CardSet(1 << FOOL_INDEX);
+    pub const TRUMPS: Self =
+        CardSet(((1 << SIZE) - 1) & !((1 << TRUMP_OFFSET) - 1) & !(1 << FOOL_INDEX));
+
+    pub fn index(card: &Card) -> u32 {
+        suit_offset(card.suit.name) + card.rank as u32 - 1
+    }
+    pub fn card(index: u32) -> Card {
+        match index {
+            ..TRUMP_OFFSET => {
+                let suit = CardSuits::AVAILABLE_SUITS[(index / SUIT_SIZE) as usize];
+                Card::new((index % SUIT_SIZE) as u8 + 1, suit)
+            }
+            _ => Card::new((index - TRUMP_OFFSET) as u8 + 1, CardSuits::Trumps),
+        }
+    }
+    pub fn single(card: &Card) -> Self {
+        CardSet(1 << Self::index(card))
+    }
+    pub fn suit(suit: CardSuits) -> Self {
+        match suit {
+            CardSuits::Trumps => Self::TRUMPS | Self::FOOL,
+            _ => CardSet(((1 << SUIT_SIZE) - 1) << suit_offset(suit)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+    pub fn contains(&self, card: &Card) -> bool {
+        self.0 & Self::single(card).0 != 0
+    }
+    pub fn insert(&mut self, card: &Card) {
+        self.0 |= Self::single(card).0;
+    }
+    pub fn remove(&mut self, card: &Card) {
+        self.0 &= !Self::single(card).0;
+    }
+    pub fn highest(&self) -> Option<u32> {
+        (!self.is_empty()).then(|| 127 - self.0.leading_zeros())
+    }
+    pub fn lowest(&self) -> Option<u32> {
+        (!self.is_empty()).then(|| self.0.trailing_zeros())
+    }
+    pub fn indexes(&self) -> impl Iterator<Item = u32> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            let index = (bits != 0).then(|| bits.trailing_zeros())?;
+            bits &= bits - 1;
+            Some(index)
+        })
+    }
+    pub fn cards(&self) -> Vec<Card> {
+        self.indexes().map(Self::card).collect()
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        CardSet(self.0 | other.0)
+    }
+}
+impl BitAnd for CardSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        CardSet(self.0 & other.0)
+    }
+}
+impl Sub for CardSet {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        CardSet(self.0 & !other.0)
+    }
+}
+impl Not for CardSet {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        CardSet(!self.0) & Self::FULL
+    }
+}
+impl From<&[Card]> for CardSet {
+    fn from(cards: &[Card]) -> Self {
+        cards.iter().collect()
+    }
+}
+impl<'a> FromIterator<&'a Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = &'a Card>>(cards: I) -> Self {
+        cards
+            .into_iter()
+            .fold(Self::EMPTY, |set, card| set | Self::single(card))
+    }
+}
+impl From<CardSet> for Vec<Card> {
+    fn from(set: CardSet) -> Self {
+        set.cards()
+    }
+}
+impl Display for CardSet {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        for card in self.cards() {
+            write!(f, "{}", card)?;
+        }
+        Ok(())
+    }
+}
+
+fn suit_offset(suit: CardSuits) -> u32 {
+    match suit {
+        CardSuits::Clubs => 0,
+        CardSuits::Diamonds => SUIT_SIZE,
+        CardSuits::Hearts => 2 * SUIT_SIZE,
+        CardSuits::Spades => 3 * SUIT_SIZE,
+        CardSuits::Trumps => TRUMP_OFFSET,
+    }
+}
+
+fn suit_of(index: u32) -> CardSet {
+    match index {
+        ..TRUMP_OFFSET => CardSet(((1 << SUIT_SIZE) - 1) << (index / SUIT_SIZE * SUIT_SIZE)),
+        _ => CardSet::TRUMPS,
+    }
+}
+
+// Same rules as allowed_cards_to_play, on indexes of the played cards
+pub fn allowed_moves(hand: CardSet, played: &[u32]) -> CardSet {
+    let fool = hand & CardSet::FOOL;
+    let cards = hand - CardSet::FOOL;
+    let Some(lead) = played.iter().copied().find(|index| *index != FOOL_INDEX) else {
+        return hand;
+    };
+    let suit = suit_of(lead);
+    let following = cards & suit;
+    if !following.is_empty() && suit != CardSet::TRUMPS {
+        return following | fool;
+    }
+    let trumps = cards & CardSet::TRUMPS;
+    if trumps.is_empty() {
+        return hand;
+    }
+    let best_trump = played
+        .iter()
+        .copied()
+        .filter(|index| CardSet::TRUMPS.0 & (1 << index) != 0)
+        .max();
+    let superior = match best_trump {
+        Some(best) => CardSet(trumps.0 & !((2 << best) - 1)),
+        None => trumps,
+    };
+    match superior.is_empty() {
+        true => trumps | fool,
+        false => superior | fool,
+    }
+}
+
+// Position of the winning card in the trick, the Fool never winning unless it is alone
+pub fn winning_index(played: &[u32]) -> Option<usize> {
+    let lead = played.iter().copied().find(|index| *index != FOOL_INDEX);
+    let Some(lead) = lead else {
+        return (!played.is_empty()).then_some(0);
+    };
+    let trumps = CardSet::TRUMPS;
+    let contenders = match played.iter().any(|index| trumps.0 & (1 << index) != 0) {
+        true => trumps,
+        false => suit_of(lead),
+    };
+    played
+        .iter()
+        .enumerate()
+        .filter(|(_, index)| contenders.0 & (1 << *index) != 0)
+        .max_by_key(|(_, index)| **index)
+        .map(|(position, _)| position)
+}
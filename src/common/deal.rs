@@ -1,96 +1,536 @@
 use rand::{
     distributions::{Distribution, Standard},
-    Rng,
+    thread_rng, Rng,
 };
 
-use crate::common::utils::display;
-
+use super::game::{check_deck, TOTAL_CARDS};
 use super::{
+    action::Action,
     bid::{Bid, Bids},
-    card::Card,
-    kitty::Kitty,
-    player::{Player, PlayerActions},
+    card::{Card, CardGetters, CardSuits, CardSuitsGetters, KING_RANK, QUEEN_RANK},
+    error::TarotError,
+    event::{Event, EventBus},
+    hand::Side,
+    handle::{available_handles, Handle},
+    kitty::{allowed_cards_to_discard, Kitty},
+    notation::{DealRecord, RecordedTrick},
+    phase::Phase,
+    player::{add_kitty_in_hand, Player, PlayerActions},
+    score::{compute_deal_score, DealScore, ScoreInput},
     taker::Taker,
-    trick::Trick,
-    utils::{get_next_index, reorder},
+    trick::{allowed_cards_to_play, Trick, TrickGetters},
+    utils::get_next_index,
+    view::SeatView,
 };
 
 const DEAL_SIZE_PLAYERS: usize = 3;
 const DEAL_SIZE_KITTY: usize = 1;
 
 pub trait DealActions {
-    fn take_bids(&mut self);
-    fn call_king(&mut self);
-    fn compose_kitty(&mut self);
-    fn play_tricks(&mut self);
-    fn compute_score(&self);
-    fn show_score(&self);
+    fn take_bids(&mut self) -> Result<(), TarotError>;
+    fn call_king(&mut self) -> Result<(), TarotError>;
+    fn compose_kitty(&mut self) -> Result<(), TarotError>;
+    fn declare_handles(&mut self) -> Result<(), TarotError>;
+    fn play_tricks(&mut self) -> Result<(), TarotError>;
+    fn compute_score(&mut self) -> Result<(), TarotError>;
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Deal {
+    pub phase: Phase,
+    pub current: usize,
     pub kitty: Kitty,
     pub players: Vec<Player>,
+    pub bid: Bid,
     pub taker: Option<Taker>,
-    pub tricks: Vec<Trick>,
+    pub partner: Option<usize>,
     pub called_king: Option<Card>,
+    pub handles: Vec<Handle>,
+    pub trick: Trick,
+    pub tricks: Vec<Trick>,
+    pub score: Option<DealScore>,
+    pub record: DealRecord,
+    pub events: EventBus,
+    fool_exchange: Option<(usize, usize)>,
 }
 impl Deal {
-    pub fn new(players: &mut Vec<Player>, deck: &mut [Card]) -> Self {
+    pub fn new(players: &mut Vec<Player>, deck: &mut [Card]) -> Result<Self, TarotError> {
+        Self::deal_with(players, deck, &mut thread_rng())
+    }
+
+    // The random generator decides when cards go to the kitty, seeding it replays the same deal
+    pub fn deal_with<R: Rng>(
+        players: &mut Vec<Player>,
+        deck: &mut [Card],
+        rng: &mut R,
+    ) -> Result<Self, TarotError> {
+        if players.is_empty() {
+            return Err(TarotError::InvalidConfiguration(String::from(
+                "A deal needs at least one player",
+            )));
+        }
+        check_deck(deck)?;
         let mut kitty = Kitty::new(players.len());
-        draw_cards(deck, players, &mut kitty);
+        draw_cards(deck, players, &mut kitty, rng);
+
+        let mut record = DealRecord::new(players.iter().map(|p| p.name.clone()).collect());
+        record.dealer = players.iter().position(|player| player.is_dealer);
+        record.hands = players.iter().map(|p| p.hand.cards.clone()).collect();
+        record.kitty = kitty.cards.clone();
 
-        Deal {
+        Ok(Deal {
+            phase: Phase::Auction,
             players: players.to_vec(), // TODO: is it necessary ?
             kitty,
+            record,
+            ..Default::default()
+        })
+    }
+
+    pub fn from_record(record: &DealRecord) -> Result<Self, TarotError> {
+        let mut players: Vec<Player> = record
+            .players
+            .iter()
+            .enumerate()
+            .map(|(seat, name)| Player::new(name.clone(), seat as u8 + 1))
+            .collect();
+        for (player, cards) in players.iter_mut().zip(record.hands.iter()) {
+            player.hand.cards = cards.clone();
+        }
+        if let Some(dealer) = record.dealer.and_then(|dealer| players.get_mut(dealer)) {
+            dealer.is_dealer = true;
+        }
+        let n_cards = record.hands.iter().flatten().count() + record.kitty.len();
+        if players.is_empty() || n_cards != TOTAL_CARDS {
+            return Err(TarotError::InvalidConfiguration(format!(
+                "The recorded deal has {} players and {} cards",
+                players.len(),
+                n_cards
+            )));
+        }
+
+        let mut deal = Deal {
+            phase: Phase::Auction,
+            kitty: Kitty {
+                cards: record.kitty.clone(),
+                ..Kitty::new(players.len())
+            },
+            record: DealRecord {
+                auction: Vec::new(),
+                called_king: None,
+                ecart: Vec::new(),
+                handles: Vec::new(),
+                tricks: Vec::new(),
+                ..record.clone()
+            },
+            players,
             ..Default::default()
+        };
+        for action in recorded_actions(record) {
+            deal.apply(action)?;
         }
+        Ok(deal)
     }
-}
-impl DealActions for Deal {
-    fn take_bids(&mut self) {
-        let mut bid = Bid::default();
-        // self.taker = collect_bids(&self.players, self.taker.clone(), &mut bid);
-        self.taker = collect_bids(&self.players, self.taker.clone(), &mut bid);
+
+    pub fn current_player(&self) -> &Player {
+        &self.players[self.current]
     }
-    fn call_king(&mut self) {
-        if self.players.len() > 4 {
-            self.called_king = Some(self.taker.clone().unwrap().player.call_king());
-            println!("\nThe called king is {}", self.called_king.unwrap());
+
+    pub fn side(&self, seat: usize) -> Side {
+        let is_taker = self.taker.as_ref().is_some_and(|taker| taker.seat == seat);
+        if is_taker || self.partner == Some(seat) {
+            Side::Attack
+        } else {
+            Side::Defense
         }
     }
-    fn compose_kitty(&mut self) {
-        match self.taker.clone().unwrap().bid {
-            Bids::GardeSans | Bids::GardeContre => {
-                println!("\n\nThe kitty stays hidden"); // TODO: move kitty in right place
+
+    pub fn legal_actions(&self) -> Vec<Action> {
+        let n_players = self.players.len();
+        match self.phase {
+            Phase::Auction => self
+                .bid
+                .get_available_bids()
+                .into_iter()
+                .map(Action::Bid)
+                .collect(),
+            Phase::KingCall => callable_kings(&self.current_player().hand.cards)
+                .into_iter()
+                .map(Action::CallKing)
+                .collect(),
+            Phase::Ecart => allowed_cards_to_discard(
+                &self.current_player().hand.cards,
+                self.kitty.max_size - self.kitty.cards.len(),
+            )
+            .into_iter()
+            .map(Action::Discard)
+            .collect(),
+            Phase::Declarations => {
+                let mut actions = Vec::from([Action::Declare(None)]);
+                for handle in available_handles(&self.current_player().hand.cards, n_players) {
+                    actions.push(Action::Declare(Some(handle)));
+                }
+                actions
             }
-            _ => {
-                println!("\n\nThe kitty contains: ");
-                display(&self.kitty.cards);
-                self.kitty.cards = self
-                    .taker
-                    .clone()
-                    .unwrap()
-                    .player
-                    .compose_kitty(&mut self.kitty)
+            Phase::Play => allowed_cards_to_play(&self.trick, &self.current_player().hand.cards)
+                .into_iter()
+                .map(Action::Play)
+                .collect(),
+            Phase::Dealing | Phase::Scoring | Phase::Done => Vec::new(),
+        }
+    }
+
+    pub fn view(&self, seat: usize) -> SeatView {
+        let taker = self.taker.as_ref().map(|taker| taker.seat);
+        // The kitty is only shown to everybody for a Petite or a Garde
+        let kitty_revealed = self.phase > Phase::KingCall
+            && matches!(
+                self.taker.as_ref().map(|taker| taker.bid),
+                Some(Bids::Petite | Bids::Garde)
+            );
+        SeatView {
+            seat,
+            players: self.record.players.clone(),
+            phase: self.phase,
+            current: self.current,
+            hand: self
+                .players
+                .get(seat)
+                .map(|player| player.hand.cards.clone())
+                .unwrap_or_default(),
+            auction: self.record.auction.clone(),
+            taker: self.taker.clone(),
+            called_king: self.called_king,
+            kitty: kitty_revealed.then(|| self.record.kitty.clone()),
+            ecart: match taker == Some(seat) {
+                true => self.record.ecart.clone(),
+                false => Vec::new(),
+            },
+            handles: self.handles.clone(),
+            trick: self.trick.clone(),
+            tricks: self.tricks.clone(),
+            legal_actions: match self.current == seat {
+                true => self.legal_actions(),
+                false => Vec::new(),
+            },
+        }
+    }
+
+    pub fn apply(&mut self, action: Action) -> Result<(), TarotError> {
+        if !accepts(self.phase, &action) {
+            return Err(TarotError::InvalidTransition {
+                phase: self.phase,
+                action,
+            });
+        }
+        if !self.legal_actions().contains(&action) {
+            return Err(TarotError::RuleViolation(action));
+        }
+
+        let seat = self.current;
+        match action {
+            Action::Bid(bid) => {
+                self.record.auction.push((seat, bid));
+                self.events.emit(Event::BidMade { seat, bid });
+                if bid != Bids::Passe {
+                    self.bid.current = bid;
+                    self.taker = Some(Taker { seat, bid });
+                }
+                self.current += 1;
+                if self.current == self.players.len() {
+                    self.end_auction();
+                }
+            }
+            Action::CallKing(king) => {
+                self.called_king = Some(king);
+                self.record.called_king = Some(king);
+                self.events.emit(Event::KingCalled { seat, king });
+                self.partner = self
+                    .players
+                    .iter()
+                    .position(|player| player.hand.cards.contains(&king))
+                    .filter(|partner| *partner != seat);
+                self.reveal_kitty();
+            }
+            Action::Discard(card) => {
+                self.players[seat].hand.cards.retain(|c| *c != card);
+                self.kitty.cards.push(card);
+                self.record.ecart.push(card);
+                if self.kitty.cards.len() == self.kitty.max_size {
+                    self.start_declarations();
+                }
+            }
+            Action::Declare(handle) => {
+                if let Some(kind) = handle {
+                    let cards = &self.players[seat].hand.cards;
+                    let handle = Handle::new(seat, kind, cards, self.players.len());
+                    self.events.emit(Event::HandleDeclared {
+                        handle: handle.clone(),
+                    });
+                    self.handles.push(handle);
+                    self.record.handles.push((seat, kind));
+                }
+                self.current += 1;
+                if self.current == self.players.len() {
+                    self.start_play();
+                }
             }
+            Action::Play(card) => {
+                self.players[seat].hand.cards.retain(|c| *c != card);
+                self.trick.played_cards.push(card);
+                self.events.emit(Event::CardPlayed { seat, card });
+                self.current = get_next_index(&self.players, seat);
+                if self.trick.played_cards.len() == self.players.len() {
+                    self.end_trick();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn end_auction(&mut self) {
+        match &self.taker {
+            None => self.phase = Phase::Done,
+            Some(taker) if self.players.len() == 5 => {
+                self.current = taker.seat;
+                self.phase = Phase::KingCall;
+            }
+            Some(_) => self.reveal_kitty(),
         }
     }
-    fn play_tricks(&mut self) {
-        if self.players[0].hand.cards.is_empty() {
+
+    fn reveal_kitty(&mut self) {
+        let Some(taker) = self.taker.clone() else {
             return;
+        };
+        match taker.bid {
+            Bids::Petite | Bids::Garde => {
+                self.events.emit(Event::KittyRevealed {
+                    cards: self.kitty.cards.clone(),
+                });
+                add_kitty_in_hand(&self.kitty.cards, &mut self.players[taker.seat].hand);
+                self.kitty.cards.clear();
+                self.current = taker.seat;
+                self.phase = Phase::Ecart;
+            }
+            _ => self.start_declarations(),
         }
-        let mut trick = Trick::default();
-        for mut player in self.players.clone() {
-            player.play(&mut trick);
+    }
+
+    fn start_declarations(&mut self) {
+        self.current = 0;
+        self.phase = Phase::Declarations;
+    }
+
+    fn start_play(&mut self) {
+        for seat in 0..self.players.len() {
+            self.players[seat].hand.side = self.side(seat);
         }
-        let winner_index = 0; // trick.get_best_played_card_index();
-        self.players = reorder(&self.players, winner_index);
+        self.current = 0;
+        self.trick = Trick::new(0);
+        self.phase = Phase::Play;
+    }
+
+    fn end_trick(&mut self) {
+        let n_players = self.players.len();
+        let trick = std::mem::take(&mut self.trick);
+        let winner = trick.winner(n_players).unwrap_or(trick.leader);
+        let is_last = self
+            .players
+            .iter()
+            .all(|player| player.hand.cards.is_empty());
+
+        for (index, card) in trick.played_cards.iter().enumerate() {
+            let seat = (trick.leader + index) % n_players;
+            // The owner keeps the Fool, except when it is played in the last trick
+            if card.is_fool() && !is_last && seat != winner {
+                self.players[seat].hand.won_cards.push(*card);
+                self.fool_exchange = Some((seat, winner));
+            } else {
+                self.players[winner].hand.won_cards.push(*card);
+            }
+        }
+        self.events.emit(Event::TrickWon {
+            seat: winner,
+            cards: trick.played_cards.clone(),
+        });
+        self.record.tricks.push(RecordedTrick {
+            leader: trick.leader,
+            cards: trick.played_cards.clone(),
+        });
         self.tricks.push(trick);
-        self.play_tricks()
+
+        if is_last {
+            self.phase = Phase::Scoring;
+        } else {
+            self.trick = Trick::new(winner);
+            self.current = winner;
+        }
+    }
+
+    // The owner of the Fool gives a low card to the side who won the trick
+    fn exchange_fool(&mut self) {
+        let Some((owner, winner)) = self.fool_exchange.take() else {
+            return;
+        };
+        if self.side(owner) == self.side(winner) {
+            return;
+        }
+        let won_cards = &mut self.players[owner].hand.won_cards;
+        if let Some(index) = won_cards
+            .iter()
+            .position(|card| !card.is_fool() && card.score() == 0.5)
+        {
+            let card = won_cards.remove(index);
+            self.players[winner].hand.won_cards.push(card);
+        }
+    }
+
+    // Players only get their own view of the deal to choose their action
+    fn ask(&self) -> Result<Action, TarotError> {
+        self.current_player().act(&self.view(self.current))
+    }
+
+    // Asks the players of the given phase for their actions until the deal moves on
+    fn drive(&mut self, phase: Phase) -> Result<(), TarotError> {
+        if self.phase < phase {
+            return Err(TarotError::InvalidPhase {
+                expected: phase,
+                found: self.phase,
+            });
+        }
+        while self.phase == phase {
+            let action = self.ask()?;
+            self.apply(action)?;
+        }
+        Ok(())
+    }
+}
+
+impl DealActions for Deal {
+    fn take_bids(&mut self) -> Result<(), TarotError> {
+        self.drive(Phase::Auction)
+    }
+    fn call_king(&mut self) -> Result<(), TarotError> {
+        self.drive(Phase::KingCall)
+    }
+    fn compose_kitty(&mut self) -> Result<(), TarotError> {
+        self.drive(Phase::Ecart)
+    }
+    fn declare_handles(&mut self) -> Result<(), TarotError> {
+        self.drive(Phase::Declarations)
+    }
+    fn play_tricks(&mut self) -> Result<(), TarotError> {
+        self.drive(Phase::Play)
+    }
+    fn compute_score(&mut self) -> Result<(), TarotError> {
+        let taker = match (&self.taker, self.phase) {
+            (Some(taker), Phase::Scoring) => taker.clone(),
+            _ => {
+                return Err(TarotError::InvalidPhase {
+                    expected: Phase::Scoring,
+                    found: self.phase,
+                })
+            }
+        };
+        self.exchange_fool();
+
+        let sides: Vec<Side> = (0..self.players.len())
+            .map(|seat| self.side(seat))
+            .collect();
+        let mut attack_cards: Vec<Card> = self
+            .players
+            .iter()
+            .enumerate()
+            .filter(|(seat, _)| sides[*seat] == Side::Attack)
+            .flat_map(|(_, player)| player.hand.won_cards.clone())
+            .collect();
+        if taker.bid != Bids::GardeContre {
+            attack_cards.extend_from_slice(&self.kitty.cards);
+        }
+
+        let last_trick = self.tricks.last();
+        let petit_au_bout = last_trick
+            .filter(|trick| trick.played_cards.iter().any(|card| card.is_petit()))
+            .and_then(|trick| trick.winner(sides.len()))
+            .map(|winner| sides[winner]);
+        let slam = self
+            .tricks
+            .iter()
+            .all(|trick| {
+                trick
+                    .winner(sides.len())
+                    .is_some_and(|winner| sides[winner] == Side::Attack)
+            })
+            .then_some(Side::Attack);
+
+        let score = compute_deal_score(&ScoreInput {
+            bid: taker.bid,
+            attack_cards: &attack_cards,
+            sides: &sides,
+            taker: taker.seat,
+            petit_au_bout,
+            handles: &self.handles,
+            slam,
+        });
+        self.events.emit(Event::DealScored {
+            score: score.clone(),
+        });
+        self.score = Some(score);
+        self.phase = Phase::Done;
+        Ok(())
+    }
+}
+
+fn accepts(phase: Phase, action: &Action) -> bool {
+    matches!(
+        (phase, action),
+        (Phase::Auction, Action::Bid(_))
+            | (Phase::KingCall, Action::CallKing(_))
+            | (Phase::Ecart, Action::Discard(_))
+            | (Phase::Declarations, Action::Declare(_))
+            | (Phase::Play, Action::Play(_))
+    )
+}
+
+// A taker holding the four kings calls a queen instead
+fn callable_kings(cards: &[Card]) -> Vec<Card> {
+    let suits = CardSuits::AVAILABLE_SUITS
+        .into_iter()
+        .filter(|suit| !suit.is_trump());
+    let kings: Vec<Card> = suits
+        .clone()
+        .map(|suit| Card::new(KING_RANK, suit))
+        .collect();
+    if kings.iter().all(|king| cards.contains(king)) {
+        suits.map(|suit| Card::new(QUEEN_RANK, suit)).collect()
+    } else {
+        kings
     }
-    fn compute_score(&self) {}
-    fn show_score(&self) {}
+}
+
+pub fn recorded_actions(record: &DealRecord) -> Vec<Action> {
+    let mut actions: Vec<Action> = record
+        .auction
+        .iter()
+        .map(|(_, bid)| Action::Bid(*bid))
+        .collect();
+    actions.extend(record.called_king.map(Action::CallKing));
+    actions.extend(record.ecart.iter().cloned().map(Action::Discard));
+    if !record.tricks.is_empty() {
+        for seat in 0..record.players.len() {
+            let handle = record
+                .handles
+                .iter()
+                .find(|(handle_seat, _)| *handle_seat == seat)
+                .map(|(_, handle)| *handle);
+            actions.push(Action::Declare(handle));
+        }
+    }
+    for trick in &record.tricks {
+        actions.extend(trick.cards.iter().cloned().map(Action::Play));
+    }
+    actions
 }
 
 #[derive(Debug, PartialEq)]
@@ -116,18 +556,27 @@ fn get_deal_size(dealing: &Dealing) -> usize {
 fn clear_cards(players: &mut Vec<Player>) {
     for player in players {
         player.hand.cards.clear();
+        player.hand.won_cards.clear();
     }
 }
 
-fn draw_kitty_or_player(kitty: &[Card], kitty_expected_size: usize) -> Dealing {
-    if kitty.len() < kitty_expected_size {
-        rand::random()
-    } else {
+fn draw_kitty_or_player<R: Rng>(
+    kitty: &[Card],
+    kitty_expected_size: usize,
+    remaining_cards: usize,
+    rng: &mut R,
+) -> Dealing {
+    let missing_cards = kitty_expected_size.saturating_sub(kitty.len());
+    if missing_cards == 0 {
         Dealing::Player
+    } else if remaining_cards <= missing_cards {
+        Dealing::Kitty
+    } else {
+        rng.gen()
     }
 }
 
-fn draw_cards(deck: &[Card], players: &mut Vec<Player>, kitty: &mut Kitty) {
+fn draw_cards<R: Rng>(deck: &[Card], players: &mut Vec<Player>, kitty: &mut Kitty, rng: &mut R) {
     let mut index: usize = 0;
     let mut dealing = Dealing::Player;
     let mut player_index = 0;
@@ -146,32 +595,6 @@ fn draw_cards(deck: &[Card], players: &mut Vec<Player>, kitty: &mut Kitty) {
             }
         }
         index = end_of_range;
-        dealing = draw_kitty_or_player(&kitty.cards, kitty.max_size);
-    }
-}
-
-fn collect_bids(players: &Vec<Player>, mut taker: Option<Taker>, bid: &mut Bid) -> Option<Taker> {
-    if players.len() <= 1 {
-        return taker;
-    }
-
-    let mut takers: Vec<Player> = Vec::new();
-    for player in players {
-        let new_bid = player.bid(bid);
-
-        if new_bid != Bids::Passe {
-            taker = Some(Taker {
-                player: player.clone(),
-                bid: new_bid,
-            });
-            takers.push(player.clone());
-        }
-        println!("{} makes the following bid: {}", player.name, new_bid);
-    }
-
-    if takers.len() > 1 {
-        collect_bids(&takers, taker, bid)
-    } else {
-        taker.clone()
+        dealing = draw_kitty_or_player(&kitty.cards, kitty.max_size, deck.len() - index, rng);
     }
 }
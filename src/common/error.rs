@@ -0,0 +1,51 @@
+use std::fmt::{Display, Formatter, Result};
+
+use super::{action::Action, notation::NotationError, phase::Phase};
+
+#[derive(Debug)]
+pub enum TarotError {
+    RuleViolation(Action),
+    InvalidTransition { phase: Phase, action: Action },
+    InvalidPhase { expected: Phase, found: Phase },
+    InvalidConfiguration(String),
+    NoAvailableChoice,
+    Io(std::io::Error),
+    Notation(NotationError),
+    Protocol(String),
+}
+impl Display for TarotError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::RuleViolation(action) => write!(f, "{} is not allowed", action),
+            Self::InvalidTransition { phase, action } => {
+                write!(f, "{} is not possible during the {} phase", action, phase)
+            }
+            Self::InvalidPhase { expected, found } => write!(
+                f,
+                "This step needs the {} phase but the deal is in the {} phase",
+                expected, found
+            ),
+            Self::InvalidConfiguration(reason) => write!(f, "Invalid configuration: {}", reason),
+            Self::NoAvailableChoice => write!(f, "No options available"),
+            Self::Io(error) => write!(f, "I/O error: {}", error),
+            Self::Notation(error) => write!(f, "{}", error),
+            Self::Protocol(reason) => write!(f, "Protocol error: {}", reason),
+        }
+    }
+}
+impl std::error::Error for TarotError {}
+impl From<std::io::Error> for TarotError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+impl From<serde_json::Error> for TarotError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Protocol(error.to_string())
+    }
+}
+impl From<NotationError> for TarotError {
+    fn from(error: NotationError) -> Self {
+        Self::Notation(error)
+    }
+}
@@ -0,0 +1,95 @@
+use super::{
+    bid::Bids,
+    card::{
+        Card, CardGetters, CardSuits, CardSuitsGetters, BIG_RANK, JACK_RANK, KING_RANK,
+        KNIGHT_RANK, LITTLE_RANK, QUEEN_RANK,
+    },
+};
+
+const MAJOR_TRUMP_RANK: u8 = 15;
+
+// Points of a hand, counted the way players usually weigh their cards before bidding
+pub fn evaluate_hand(cards: &[Card]) -> f64 {
+    let trumps: Vec<&Card> = cards.iter().filter(|card| card.suit.is_trump()).collect();
+    let n_trumps = trumps.iter().filter(|card| !card.is_fool()).count();
+
+    let mut points = 0.0;
+    for trump in &trumps {
+        points += match trump.rank {
+            BIG_RANK => 10.0,
+            LITTLE_RANK => little_points(n_trumps),
+            _ if trump.is_fool() => 8.0,
+            rank if rank >= MAJOR_TRUMP_RANK => 3.0,
+            _ => 2.0,
+        };
+    }
+
+    for suit in CardSuits::AVAILABLE_SUITS
+        .into_iter()
+        .filter(|suit| !suit.is_trump())
+    {
+        let ranks: Vec<u8> = cards
+            .iter()
+            .filter(|card| card.suit.name == suit)
+            .map(|card| card.rank)
+            .collect();
+        points += suit_points(&ranks, n_trumps);
+    }
+    points
+}
+
+// The Petit is a liability when there are not enough trumps to protect it
+fn little_points(n_trumps: usize) -> f64 {
+    match n_trumps {
+        ..=4 => 0.0,
+        5 => 5.0,
+        6 => 7.0,
+        _ => 9.0,
+    }
+}
+
+fn suit_points(ranks: &[u8], n_trumps: usize) -> f64 {
+    let has = |rank: u8| ranks.contains(&rank);
+    let mut points = 0.0;
+    if has(KING_RANK) {
+        points += 6.0;
+    }
+    if has(QUEEN_RANK) {
+        points += if has(KING_RANK) { 4.0 } else { 2.0 };
+    }
+    if has(KNIGHT_RANK) {
+        points += 2.0;
+    }
+    if has(JACK_RANK) {
+        points += 1.0;
+    }
+    // Long suits end up as masters once the trumps are out, short ones let the taker cut
+    points += match ranks.len() {
+        0 if n_trumps >= 4 => 6.0,
+        1 if n_trumps >= 4 && !has(KING_RANK) => 3.0,
+        length if length >= 5 => (length as f64 - 4.0) * 2.0 + 3.0,
+        _ => 0.0,
+    };
+    points
+}
+
+// A hand has more cards with fewer players, and a partner helps with five players
+fn thresholds(n_players: usize) -> [f64; 4] {
+    match n_players {
+        ..=3 => [56.0, 70.0, 84.0, 94.0],
+        4 => [42.0, 56.0, 70.0, 80.0],
+        _ => [34.0, 46.0, 58.0, 68.0],
+    }
+}
+
+pub fn ideal_bid(cards: &[Card], n_players: usize) -> Bids {
+    let points = evaluate_hand(cards);
+    let [petite, garde, garde_sans, garde_contre] = thresholds(n_players);
+    match points {
+        points if points >= garde_contre => Bids::GardeContre,
+        points if points >= garde_sans => Bids::GardeSans,
+        points if points >= garde => Bids::Garde,
+        points if points >= petite => Bids::Petite,
+        _ => Bids::Passe,
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use super::{bid::Bids, card::Card, handle::Handle, score::DealScore};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Event {
+    DealerChosen { name: String },
+    DealStarted { players: Vec<String> },
+    BidMade { seat: usize, bid: Bids },
+    KingCalled { seat: usize, king: Card },
+    KittyRevealed { cards: Vec<Card> },
+    HandleDeclared { handle: Handle },
+    CardPlayed { seat: usize, card: Card },
+    TrickWon { seat: usize, cards: Vec<Card> },
+    DealScored { score: DealScore },
+}
+
+pub trait Observer {
+    fn notify(&mut self, event: &Event);
+}
+
+pub type SharedObserver = Arc<Mutex<dyn Observer + Send>>;
+
+#[derive(Default, Clone)]
+pub struct EventBus {
+    observers: Vec<SharedObserver>,
+}
+impl Debug for EventBus {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "EventBus({} observers)", self.observers.len())
+    }
+}
+impl EventBus {
+    pub fn subscribe(&mut self, observer: SharedObserver) {
+        self.observers.push(observer);
+    }
+    pub fn emit(&self, event: Event) {
+        for observer in &self.observers {
+            // A poisoned observer already panicked once, the others still get the event
+            if let Ok(mut observer) = observer.lock() {
+                observer.notify(&event);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EventLog {
+    pub events: Vec<Event>,
+}
+impl Observer for EventLog {
+    fn notify(&mut self, event: &Event) {
+        self.events.push(event.clone());
+    }
+}
@@ -3,21 +3,24 @@ use rand::thread_rng;
 
 use super::card::{Card, CardSuits};
 use super::deal::Deal;
+use super::error::TarotError;
+use super::event::{Event, EventBus};
 use super::player::Player;
 use super::utils::{get_next_index, random_int_in_range, reorder};
 
 const NUMBER_CARDS_BY_SUIT: usize = 14;
 const NUMBER_TRUMP_CARDS: usize = 22;
 
-const TOTAL_CARDS: usize = 78;
+pub const TOTAL_CARDS: usize = 78;
 const MIN_NUMBER_CARDS_SPLIT: usize = 3;
 const MAX_NUMBER_CARDS_SPLIT: usize = TOTAL_CARDS - MIN_NUMBER_CARDS_SPLIT;
 
 pub trait GameActions {
-    fn update_dealer(&mut self);
-    fn split_deck(&mut self);
-    fn collect_deck(&mut self, players: &[Player]);
-    fn reorder_players(&mut self);
+    fn update_dealer(&mut self) -> Result<(), TarotError>;
+    fn split_deck(&mut self) -> Result<(), TarotError>;
+    fn collect_deck(&mut self, deal: &Deal) -> Result<(), TarotError>;
+    fn reorder_players(&mut self) -> Result<(), TarotError>;
+    fn new_deal(&mut self) -> Result<Deal, TarotError>;
 }
 
 #[derive(Debug)]
@@ -25,6 +28,7 @@ pub struct Game {
     pub players: Vec<Player>,
     pub deck: Vec<Card>,
     pub deals: Vec<Deal>,
+    pub events: EventBus,
 }
 impl Default for Game {
     fn default() -> Self {
@@ -37,43 +41,59 @@ impl Game {
             players: create_players(n_players),
             deck: create_deck(),
             deals: Vec::new(),
+            events: EventBus::default(),
         }
     }
 }
 impl GameActions for Game {
-    fn split_deck(&mut self) {
+    fn split_deck(&mut self) -> Result<(), TarotError> {
+        check_deck(&self.deck)?;
         let split_index = random_int_in_range(1, MAX_NUMBER_CARDS_SPLIT);
         let mut new_deck = Vec::new();
         new_deck.extend_from_slice(&self.deck[split_index..]);
         new_deck.extend_from_slice(&self.deck[..split_index]);
         self.deck = new_deck;
+        Ok(())
     }
 
-    fn collect_deck(&mut self, players: &[Player]) {
+    fn collect_deck(&mut self, deal: &Deal) -> Result<(), TarotError> {
         let mut deck = Vec::new();
-        for player in players {
-            match player.hand.cards.is_empty() {
-                true => deck.extend(player.hand.won_cards.clone()),
-                false => deck.extend(player.hand.cards.clone()),
-            }
+        for player in &deal.players {
+            deck.extend(player.hand.won_cards.clone());
+            deck.extend(player.hand.cards.clone());
         }
+        deck.extend(deal.kitty.cards.clone());
+        check_deck(&deck)?;
         self.deck = deck;
+        Ok(())
     }
-    fn update_dealer(&mut self) {
-        let index = find_dealer(&self.players);
+    fn update_dealer(&mut self) -> Result<(), TarotError> {
+        let index = find_dealer(&self.players)?;
         self.players[index].is_dealer = false;
 
         let next_index = get_next_index(&self.players, index);
         self.players[next_index].is_dealer = true;
 
-        println!("The dealer is {}", self.players[next_index].name);
+        self.events.emit(Event::DealerChosen {
+            name: self.players[next_index].name.clone(),
+        });
+        Ok(())
     }
-    fn reorder_players(&mut self) {
-        let dealer_index = find_dealer(&self.players);
+    fn reorder_players(&mut self) -> Result<(), TarotError> {
+        let dealer_index = find_dealer(&self.players)?;
         let start_index = get_next_index(&self.players, dealer_index);
         let new_players = reorder(&self.players, start_index);
         self.players.clear();
         self.players.extend_from_slice(&new_players);
+        Ok(())
+    }
+    fn new_deal(&mut self) -> Result<Deal, TarotError> {
+        let mut deal = Deal::new(&mut self.players, &mut self.deck)?;
+        deal.events = self.events.clone();
+        self.events.emit(Event::DealStarted {
+            players: self.players.iter().map(|p| p.name.clone()).collect(),
+        });
+        Ok(deal)
     }
 }
 
@@ -90,6 +110,9 @@ fn generate_players(n_players: u8) -> Vec<Player> {
 }
 
 fn set_first_dealer(players: &mut [Player]) {
+    if players.is_empty() {
+        return;
+    }
     let index = random_int_in_range(0, players.len());
     players[index].is_dealer = true;
 }
@@ -120,12 +143,32 @@ fn generate_suits(deck: &mut Vec<Card>) {
 }
 
 pub fn create_deck() -> Vec<Card> {
+    let mut deck = new_deck();
+    deck.shuffle(&mut thread_rng());
+    deck
+}
+
+pub fn new_deck() -> Vec<Card> {
     let mut deck = Vec::new();
     generate_suits(&mut deck);
-    deck.shuffle(&mut thread_rng());
-    deck.to_vec()
+    deck
 }
 
-pub fn find_dealer(players: &[Player]) -> usize {
-    players.iter().position(|player| player.is_dealer).unwrap()
+pub fn find_dealer(players: &[Player]) -> Result<usize, TarotError> {
+    players
+        .iter()
+        .position(|player| player.is_dealer)
+        .ok_or_else(|| TarotError::InvalidConfiguration(String::from("No dealer at the table")))
+}
+
+pub fn check_deck(deck: &[Card]) -> Result<(), TarotError> {
+    if deck.len() == TOTAL_CARDS {
+        Ok(())
+    } else {
+        Err(TarotError::InvalidConfiguration(format!(
+            "The deck contains {} cards instead of {}",
+            deck.len(),
+            TOTAL_CARDS
+        )))
+    }
 }
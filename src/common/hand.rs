@@ -1,15 +1,28 @@
-use super::card::Card;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Copy, Clone, PartialEq)]
-enum Side {
+use super::{
+    card::Card,
+    locale::{text, Messages},
+};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Side {
     Attack,
     #[default]
     Defense,
 }
+impl Side {
+    pub fn label(&self) -> &'static str {
+        text(match self {
+            Self::Attack => Messages::Attack,
+            Self::Defense => Messages::Defense,
+        })
+    }
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct Hand {
     pub cards: Vec<Card>,
     pub won_cards: Vec<Card>,
-    side: Side,
+    pub side: Side,
 }
@@ -0,0 +1,99 @@
+use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    card::{Card, CardGetters, CardSuitsGetters},
+    locale::{text, Messages},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Handles {
+    Simple,
+    Double,
+    Triple,
+}
+impl Display for Handles {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl FromStr for Handles {
+    type Err = String;
+
+    fn from_str(name: &str) -> std::result::Result<Self, Self::Err> {
+        match name {
+            "Simple" => Ok(Self::Simple),
+            "Double" => Ok(Self::Double),
+            "Triple" => Ok(Self::Triple),
+            _ => Err(format!("Unknown handle '{}'", name)),
+        }
+    }
+}
+impl Handles {
+    pub const AVAILABLE_HANDLES: [Self; 3] = [Self::Simple, Self::Double, Self::Triple];
+
+    pub fn required_trumps(&self, n_players: usize) -> usize {
+        let (simple, double, triple) = match n_players {
+            ..=3 => (13, 15, 18),
+            4 => (10, 13, 15),
+            _ => (8, 10, 13),
+        };
+        match self {
+            Self::Simple => simple,
+            Self::Double => double,
+            Self::Triple => triple,
+        }
+    }
+    pub fn label(&self) -> &'static str {
+        text(match self {
+            Self::Simple => Messages::SimpleHandle,
+            Self::Double => Messages::DoubleHandle,
+            Self::Triple => Messages::TripleHandle,
+        })
+    }
+    pub fn bonus(&self) -> f64 {
+        match self {
+            Self::Simple => 20.0,
+            Self::Double => 30.0,
+            Self::Triple => 40.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Handle {
+    pub seat: usize,
+    pub kind: Handles,
+    pub cards: Vec<Card>,
+}
+impl Handle {
+    pub fn new(seat: usize, kind: Handles, cards: &[Card], n_players: usize) -> Self {
+        Handle {
+            seat,
+            kind,
+            cards: shown_trumps(cards, kind.required_trumps(n_players)),
+        }
+    }
+}
+
+pub fn available_handles(cards: &[Card], n_players: usize) -> Vec<Handles> {
+    let n_trumps = cards.iter().filter(|card| card.suit.is_trump()).count();
+    Handles::AVAILABLE_HANDLES
+        .into_iter()
+        .filter(|handle| n_trumps >= handle.required_trumps(n_players))
+        .collect()
+}
+
+// The Fool is only shown when the other trumps are not enough
+fn shown_trumps(cards: &[Card], required: usize) -> Vec<Card> {
+    let mut trumps: Vec<Card> = cards
+        .iter()
+        .filter(|card| card.suit.is_trump())
+        .cloned()
+        .collect();
+    trumps.sort_unstable_by_key(|card| (card.is_fool(), card.rank));
+    trumps.truncate(required);
+    trumps
+}
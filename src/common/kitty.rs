@@ -1,19 +1,27 @@
 use crate::common::{
-    card::{CardGetters, CardSuits, KING_RANK},
-    utils::display,
+    card::{CardGetters, CardSuitsGetters, KING_RANK},
+    locale::{fill, text, Messages},
+    render::{color, paint_all},
 };
 
 use super::{
     card::Card,
-    utils::{select, subtract},
+    error::TarotError,
+    utils::{select_card, HintSource, Lines},
 };
 
 pub trait KittyActions {
-    fn bot_compose(&mut self, cards: &[Card]) -> Vec<Card>;
-    fn human_compose(&mut self, cards: &mut Vec<Card>) -> Vec<Card>;
+    fn bot_discard(&self, cards: &[Card], allowed_cards: &[Card]) -> Result<Card, TarotError>;
+    fn human_discard(
+        &self,
+        cards: &[Card],
+        allowed_cards: &[Card],
+        hint: Option<HintSource>,
+        input: Option<&Lines>,
+    ) -> Result<Card, TarotError>;
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Kitty {
     pub cards: Vec<Card>,
     pub max_size: usize,
@@ -32,32 +40,44 @@ impl Kitty {
 }
 
 impl KittyActions for Kitty {
-    fn bot_compose(&mut self, cards: &[Card]) -> Vec<Card> {
-        println!("Bot compose kitty");
-        cards.to_vec()
+    fn bot_discard(&self, _cards: &[Card], allowed_cards: &[Card]) -> Result<Card, TarotError> {
+        allowed_cards
+            .iter()
+            .min_by(|a, b| a.score().total_cmp(&b.score()))
+            .copied()
+            .ok_or(TarotError::NoAvailableChoice)
     }
 
-    fn human_compose(&mut self, cards: &mut Vec<Card>) -> Vec<Card> {
-        // TODO: implement way to correct the kitty
-        let mut new_kitty: Vec<Card> = Vec::new();
-        while new_kitty.len() < self.max_size {
-            println!("\nThe building kitty contains: ");
-            display(&new_kitty);
-            // get diff of vects cards and new_kitty
-            subtract(cards, &new_kitty);
-            let available_cards = cards.clone();
-
-            let card = select(Some("Compose your kitty"), Some(available_cards)).unwrap();
-            if (card.suit.name != CardSuits::Trumps && card.rank == KING_RANK) || card.is_oudler() {
-                println!("You cannot select a King or an Oudler for the kitty.");
-                continue;
-            } else {
-                new_kitty.push(card);
-            }
+    fn human_discard(
+        &self,
+        cards: &[Card],
+        allowed_cards: &[Card],
+        hint: Option<HintSource>,
+        input: Option<&Lines>,
+    ) -> Result<Card, TarotError> {
+        if !self.cards.is_empty() {
+            let ecart = paint_all(&self.cards, color());
+            println!("\n{}", fill(Messages::BuildingKitty, &[&ecart]));
         }
-        println!("\nThe new kitty is:");
-        display(&new_kitty);
-        self.cards = new_kitty;
-        self.cards.clone()
+        select_card(
+            text(Messages::ComposeKitty),
+            cards,
+            allowed_cards,
+            hint,
+            input,
+        )
+    }
+}
+
+// Kings and Oudlers can never be discarded, trumps only when nothing else is left
+pub fn allowed_cards_to_discard(cards: &[Card], remaining: usize) -> Vec<Card> {
+    let (trumps, others): (Vec<Card>, Vec<Card>) = cards
+        .iter()
+        .filter(|card| !card.is_oudler() && (card.suit.is_trump() || card.rank != KING_RANK))
+        .partition(|card| card.suit.is_trump());
+    if others.len() >= remaining {
+        others
+    } else {
+        [others, trumps].concat()
     }
 }
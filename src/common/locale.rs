@@ -0,0 +1,541 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// Checked in this order, the first one set wins like for gettext
+const LOCALE_VARIABLES: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(Languages::English as u8);
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum Languages {
+    #[default]
+    English,
+    French,
+}
+impl FromStr for Languages {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "en" | "english" => Ok(Self::English),
+            "fr" | "french" | "français" => Ok(Self::French),
+            _ => Err(format!("Unknown language '{}'", name)),
+        }
+    }
+}
+impl Languages {
+    // A locale such as fr_FR.UTF-8, the C and POSIX locales have no language
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let language = locale.split(['_', '.', '@', '-']).next()?;
+        language.parse().ok()
+    }
+
+    pub fn from_env() -> Self {
+        LOCALE_VARIABLES
+            .iter()
+            .filter_map(|variable| std::env::var(variable).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Self::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    pub fn text(&self, message: Messages) -> &'static str {
+        match self {
+            Self::English => english(message),
+            Self::French => french(message),
+        }
+    }
+
+    // Every {} of the message is replaced by the next argument
+    pub fn fill(&self, message: Messages, args: &[&dyn Display]) -> String {
+        let mut args = args.iter();
+        let mut parts = self.text(message).split("{}");
+        let mut filled = parts.next().unwrap_or_default().to_string();
+        for part in parts {
+            if let Some(arg) = args.next() {
+                filled += &arg.to_string();
+            }
+            filled += part;
+        }
+        filled
+    }
+}
+
+pub fn set_language(language: Languages) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Languages {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Languages::French,
+        _ => Languages::English,
+    }
+}
+
+pub fn text(message: Messages) -> &'static str {
+    language().text(message)
+}
+
+pub fn fill(message: Messages, args: &[&dyn Display]) -> String {
+    language().fill(message, args)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Messages {
+    King,
+    Queen,
+    Knight,
+    Jack,
+    Fool,
+    KingInitial,
+    QueenInitial,
+    KnightInitial,
+    JackInitial,
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+    Trumps,
+    Passe,
+    Petite,
+    Garde,
+    GardeSans,
+    GardeContre,
+    SimpleHandle,
+    DoubleHandle,
+    TripleHandle,
+    NoHandle,
+    Attack,
+    Defense,
+    Won,
+    Lost,
+    ActionBid,
+    ActionCall,
+    ActionDiscard,
+    ActionDeclare,
+    ActionDeclareNothing,
+    ActionPlay,
+    SelectOption,
+    SelectOptionOrHint,
+    InvalidIndex,
+    InvalidNumber,
+    HintIs,
+    NoHint,
+    NoOptions,
+    YourCards,
+    WhatIsYourBid,
+    WhichKing,
+    DeclareHandle,
+    ComposeKitty,
+    WhichCard,
+    TrickContains,
+    BuildingKitty,
+    DealerIs,
+    BidMade,
+    KingCalled,
+    KittyContains,
+    HandleShown,
+    CardPlayed,
+    TrickWon,
+    DealResult,
+    PetitAuBout,
+    PlayerScore,
+    Greeting,
+    NobodyBid,
+    TakerIs,
+    Thanks,
+    AnalysisTitle,
+    NoMistake,
+    PlayerMistake,
+    ReasonBid,
+    ReasonCallOwnKing,
+    ReasonCallKing,
+    ReasonLastOfSuit,
+    ReasonCheapest,
+    ReasonHandle,
+    ReasonNoHandle,
+    ReasonFool,
+    ReasonSafePetit,
+    ReasonBigSavesPetit,
+    ReasonMaster,
+    ReasonOnlyCard,
+    ReasonLeadLow,
+    ReasonLostTrick,
+    MistakeCost,
+    MistakeBid,
+    MistakeEcart,
+    MistakePlay,
+    MistakeLost,
+    LogDeals,
+    LogDealStarts,
+    LogBid,
+    LogKingCalled,
+    LogKitty,
+    LogHandle,
+    LogCardPlayed,
+    LogTrickWon,
+    LogDealScored,
+    ScreenDeal,
+    ScreenTaker,
+    ScreenCalling,
+    ScreenTrickWinner,
+    ScreenHand,
+    ScreenScores,
+    ScreenAuction,
+    ScreenKitty,
+    ScreenEvents,
+    ScreenHelp,
+    ScreenNobodyBid,
+    ScreenNextDeal,
+    ScreenIllegalCard,
+    ServerWaiting,
+    ServerJoined,
+    ServerLeft,
+    ServerGameOver,
+    DefaultName,
+    ClientJoined,
+    ClientClosed,
+    NotInLobby,
+    LobbyOpen,
+    HttpOpen,
+    NoProfiles,
+    UnknownProfile,
+    NoSuchTable,
+    TableFull,
+    AlreadySeated,
+    NotSeated,
+    InvalidTable,
+    EarlyReveal,
+    SessionRunning,
+    SessionOver,
+    SatDown,
+    LeftTable,
+    ClientSeated,
+    TableLine,
+    TableWaiting,
+    TablePlaying,
+    TableOver,
+    NoTables,
+    LobbyHelp,
+    UnknownCommand,
+    Watching,
+    HandsOf,
+    ServerBack,
+    NotYourTurn,
+    TableTaken,
+    UnknownToken,
+    ClientToken,
+}
+
+fn english(message: Messages) -> &'static str {
+    match message {
+        Messages::King => "King",
+        Messages::Queen => "Queen",
+        Messages::Knight => "Knight",
+        Messages::Jack => "Jack",
+        Messages::Fool => "Fool",
+        Messages::KingInitial => "K",
+        Messages::QueenInitial => "Q",
+        Messages::KnightInitial => "C",
+        Messages::JackInitial => "J",
+        Messages::Clubs => "Clubs",
+        Messages::Diamonds => "Diamonds",
+        Messages::Hearts => "Hearts",
+        Messages::Spades => "Spades",
+        Messages::Trumps => "Trumps",
+        Messages::Passe => "Pass",
+        Messages::Petite => "Petite",
+        Messages::Garde => "Garde",
+        Messages::GardeSans => "Garde Sans",
+        Messages::GardeContre => "Garde Contre",
+        Messages::SimpleHandle => "Simple handle",
+        Messages::DoubleHandle => "Double handle",
+        Messages::TripleHandle => "Triple handle",
+        Messages::NoHandle => "No handle",
+        Messages::Attack => "attack",
+        Messages::Defense => "defense",
+        Messages::Won => "won",
+        Messages::Lost => "lost",
+        Messages::ActionBid => "Bid {}",
+        Messages::ActionCall => "Call{}",
+        Messages::ActionDiscard => "Discard{}",
+        Messages::ActionDeclare => "Declare a {}",
+        Messages::ActionDeclareNothing => "Declare no handle",
+        Messages::ActionPlay => "Play{}",
+        Messages::SelectOption => "Select an option between 0 and {}",
+        Messages::SelectOptionOrHint => "Select an option between 0 and {} (or ? for a hint)",
+        Messages::InvalidIndex => "Invalid input. Please enter a number lower or equal than {}",
+        Messages::InvalidNumber => "Invalid input. Please enter a number.",
+        Messages::HintIs => "Hint: {}",
+        Messages::NoHint => "No hint available",
+        Messages::NoOptions => "No options available",
+        Messages::YourCards => "Your cards:",
+        Messages::WhatIsYourBid => "What is your bid?",
+        Messages::WhichKing => "Which king do you call?",
+        Messages::DeclareHandle => "Do you declare a handle?",
+        Messages::ComposeKitty => "Compose your kitty",
+        Messages::WhichCard => "Which card do you play?",
+        Messages::TrickContains => "The trick contains: {}",
+        Messages::BuildingKitty => "The building kitty contains: {}",
+        Messages::DealerIs => "The dealer is {}",
+        Messages::BidMade => "{} makes the following bid: {}",
+        Messages::KingCalled => "{} calls {}",
+        Messages::KittyContains => "The kitty contains: {}",
+        Messages::HandleShown => "{} shows a {}: {}",
+        Messages::CardPlayed => "{} plays {}",
+        Messages::TrickWon => "{} wins the trick: {}",
+        Messages::DealResult => {
+            "The attack made {} points with {} oudler(s), {} needed: the {} is {}"
+        }
+        Messages::PetitAuBout => "Petit au bout for the {}",
+        Messages::PlayerScore => "{}: {}",
+        Messages::Greeting => "Let's play Tarot!",
+        Messages::NobodyBid => "Nobody made a bid. Starting a new deal...",
+        Messages::TakerIs => "The taker is {} with a bid of {}",
+        Messages::Thanks => "Thanks for playing !",
+        Messages::AnalysisTitle => "Analysis of the deal:",
+        Messages::NoMistake => "No mistake found",
+        Messages::PlayerMistake => "{}: {}",
+        Messages::ReasonBid => "{} oudlers, {} trumps, a hand worth {} points",
+        Messages::ReasonCallOwnKing => "you hold every other choice, you will play alone",
+        Messages::ReasonCallKing => "your partner will be the one holding{}",
+        Messages::ReasonLastOfSuit => "it is your last {}, discarding it leaves a void",
+        Messages::ReasonCheapest => "it is one of your cheapest cards, worth {}",
+        Messages::ReasonHandle => "{} trumps are worth the bonus",
+        Messages::ReasonNoHandle => "you do not have enough trumps for a handle",
+        Messages::ReasonFool => "the Fool keeps its points whoever wins the trick",
+        Messages::ReasonSafePetit => "the Petit cannot be caught anymore, take the trick with it",
+        Messages::ReasonBigSavesPetit => {
+            "the 21 takes the trick and draws the trumps to save the Petit"
+        }
+        Messages::ReasonMaster => "nobody left can beat it, the trick is yours",
+        Messages::ReasonOnlyCard => "it is the only card you can play",
+        Messages::ReasonLeadLow => "lead a low card and keep your strong ones",
+        Messages::ReasonLostTrick => "the trick is lost for you, give your cheapest card",
+        Messages::MistakeCost => "{} (about {} points)",
+        Messages::MistakeBid => "Bid {} where the evaluator advised {} for a hand worth {} points",
+        Messages::MistakeEcart => "The écart{} leaves a weaker hand than the bot's{}",
+        Messages::MistakePlay => "Played{} where{} was better",
+        Messages::MistakeLost => "Lost{} to {} in trick {}",
+        Messages::LogDeals => "{} deals",
+        Messages::LogDealStarts => "Deal {} starts",
+        Messages::LogBid => "{}: {}",
+        Messages::LogKingCalled => "{} calls {}",
+        Messages::LogKitty => "Kitty: {}",
+        Messages::LogHandle => "{} shows a {}",
+        Messages::LogCardPlayed => "{} plays {}",
+        Messages::LogTrickWon => "{} wins the trick",
+        Messages::LogDealScored => "The {} is {} with {} points",
+        Messages::ScreenDeal => "Deal {}",
+        Messages::ScreenTaker => " - {} by {}",
+        Messages::ScreenCalling => ", calling {}",
+        Messages::ScreenTrickWinner => "{} wins the trick",
+        Messages::ScreenHand => "Your hand:",
+        Messages::ScreenScores => "Scores",
+        Messages::ScreenAuction => "Auction",
+        Messages::ScreenKitty => "Kitty: {}",
+        Messages::ScreenEvents => "Events",
+        Messages::ScreenHelp => "←/→ move   Enter choose   ? hint   q quit",
+        Messages::ScreenNobodyBid => "Nobody made a bid. Press Enter for a new deal, q to quit",
+        Messages::ScreenNextDeal => "Press Enter for the next deal, q to quit",
+        Messages::ScreenIllegalCard => "This card cannot be played now",
+        Messages::ServerWaiting => "Waiting for {} player(s) on {}",
+        Messages::ServerJoined => "{} joined the table",
+        Messages::ServerLeft => "{} left the table, a bot plays until they come back",
+        Messages::ServerGameOver => "The game is over, thanks for playing !",
+        Messages::DefaultName => "Guest",
+        Messages::ClientJoined => "Connected to {} as {}",
+        Messages::ClientClosed => "The server closed the connection",
+        Messages::NotInLobby => "Only an action is expected at this table",
+        Messages::LobbyOpen => "Lobby open on {}",
+        Messages::HttpOpen => "The HTTP API listens on http://{}",
+        Messages::NoProfiles => "No game recorded yet in {}",
+        Messages::UnknownProfile => "No profile for {}",
+        Messages::NoSuchTable => "There is no table {}",
+        Messages::TableFull => "Table {} has no free seat",
+        Messages::AlreadySeated => "Leave your table first",
+        Messages::NotSeated => "Sit at a table first",
+        Messages::InvalidTable => "A table has 3 to 5 players, no more seats for remote players than players, and at least one deal",
+        Messages::EarlyReveal => "A table with remote players only shows the hands to its spectators at the end of the deal",
+        Messages::SessionRunning => "The session of the table is not over",
+        Messages::SessionOver => "The session of table {} is over, /restart to play again",
+        Messages::SatDown => "{} sits at table {}",
+        Messages::LeftTable => "{} leaves table {}",
+        Messages::ClientSeated => "Seated at table {} as player {}",
+        Messages::TableLine => "Table {}: {} players, {}/{} seats taken, {} deal(s), {} spectator(s), {} {}",
+        Messages::TableWaiting => "waiting",
+        Messages::TablePlaying => "playing",
+        Messages::TableOver => "over",
+        Messages::NoTables => "No table yet, /create <players> <seats> [deals] opens one",
+        Messages::LobbyHelp => "Commands: /tables, /create <players> <seats> [deals] [end|<tricks>], /sit <table>, /leave, /restart, /say <text>, /watch <table>",
+        Messages::UnknownCommand => "Unknown command '{}'",
+        Messages::Watching => "Watching table {}, /leave to stop",
+        Messages::HandsOf => "Hand of {}:",
+        Messages::ServerBack => "{} is back at the table",
+        Messages::NotYourTurn => "Wait for your turn",
+        Messages::TableTaken => "Every seat of the table is taken",
+        Messages::UnknownToken => "Unknown session token",
+        Messages::ClientToken => "Your session token is {}, join with --token {} to come back",
+    }
+}
+
+fn french(message: Messages) -> &'static str {
+    match message {
+        Messages::King => "Roi",
+        Messages::Queen => "Dame",
+        Messages::Knight => "Cavalier",
+        Messages::Jack => "Valet",
+        Messages::Fool => "Excuse",
+        Messages::KingInitial => "R",
+        Messages::QueenInitial => "D",
+        Messages::KnightInitial => "C",
+        Messages::JackInitial => "V",
+        Messages::Clubs => "Trèfle",
+        Messages::Diamonds => "Carreau",
+        Messages::Hearts => "Cœur",
+        Messages::Spades => "Pique",
+        Messages::Trumps => "Atouts",
+        Messages::Passe => "Passe",
+        Messages::Petite => "Petite",
+        Messages::Garde => "Garde",
+        Messages::GardeSans => "Garde Sans",
+        Messages::GardeContre => "Garde Contre",
+        Messages::SimpleHandle => "poignée simple",
+        Messages::DoubleHandle => "poignée double",
+        Messages::TripleHandle => "poignée triple",
+        Messages::NoHandle => "Pas de poignée",
+        Messages::Attack => "attaque",
+        Messages::Defense => "défense",
+        Messages::Won => "gagnée",
+        Messages::Lost => "perdue",
+        Messages::ActionBid => "Annoncer {}",
+        Messages::ActionCall => "Appeler{}",
+        Messages::ActionDiscard => "Écarter{}",
+        Messages::ActionDeclare => "Déclarer une {}",
+        Messages::ActionDeclareNothing => "Ne pas déclarer de poignée",
+        Messages::ActionPlay => "Jouer{}",
+        Messages::SelectOption => "Choisissez une option entre 0 et {}",
+        Messages::SelectOptionOrHint => {
+            "Choisissez une option entre 0 et {} (ou ? pour un conseil)"
+        }
+        Messages::InvalidIndex => "Saisie invalide. Entrez un nombre inférieur ou égal à {}",
+        Messages::InvalidNumber => "Saisie invalide. Entrez un nombre.",
+        Messages::HintIs => "Conseil : {}",
+        Messages::NoHint => "Aucun conseil disponible",
+        Messages::NoOptions => "Aucune option disponible",
+        Messages::YourCards => "Vos cartes :",
+        Messages::WhatIsYourBid => "Quelle est votre enchère ?",
+        Messages::WhichKing => "Quel roi appelez-vous ?",
+        Messages::DeclareHandle => "Déclarez-vous une poignée ?",
+        Messages::ComposeKitty => "Composez votre écart",
+        Messages::WhichCard => "Quelle carte jouez-vous ?",
+        Messages::TrickContains => "Le pli contient : {}",
+        Messages::BuildingKitty => "L'écart en cours contient : {}",
+        Messages::DealerIs => "{} distribue",
+        Messages::BidMade => "{} annonce : {}",
+        Messages::KingCalled => "{} appelle {}",
+        Messages::KittyContains => "Le chien contient : {}",
+        Messages::HandleShown => "{} montre une {} : {}",
+        Messages::CardPlayed => "{} joue {}",
+        Messages::TrickWon => "{} remporte le pli : {}",
+        Messages::DealResult => {
+            "L'attaque a fait {} points avec {} bout(s), {} nécessaires : la {} est {}"
+        }
+        Messages::PetitAuBout => "Petit au bout pour la {}",
+        Messages::PlayerScore => "{} : {}",
+        Messages::Greeting => "Jouons au Tarot !",
+        Messages::NobodyBid => "Personne n'a pris. Nouvelle donne...",
+        Messages::TakerIs => "{} prend avec une {}",
+        Messages::Thanks => "Merci d'avoir joué !",
+        Messages::AnalysisTitle => "Analyse de la donne :",
+        Messages::NoMistake => "Aucune erreur trouvée",
+        Messages::PlayerMistake => "{} : {}",
+        Messages::ReasonBid => "{} bouts, {} atouts, une main qui vaut {} points",
+        Messages::ReasonCallOwnKing => "vous avez tous les autres choix, vous jouerez seul",
+        Messages::ReasonCallKing => "votre partenaire sera celui qui a{}",
+        Messages::ReasonLastOfSuit => "c'est votre dernier {}, l'écarter vous fait une coupe",
+        Messages::ReasonCheapest => "c'est une de vos cartes les plus faibles, elle vaut {}",
+        Messages::ReasonHandle => "{} atouts valent la prime",
+        Messages::ReasonNoHandle => "vous n'avez pas assez d'atouts pour une poignée",
+        Messages::ReasonFool => "l'Excuse garde ses points quel que soit le gagnant du pli",
+        Messages::ReasonSafePetit => "le Petit ne peut plus être pris, remportez le pli avec lui",
+        Messages::ReasonBigSavesPetit => {
+            "le 21 remporte le pli et fait tomber les atouts pour sauver le Petit"
+        }
+        Messages::ReasonMaster => "personne ne peut plus la battre, le pli est à vous",
+        Messages::ReasonOnlyCard => "c'est la seule carte que vous pouvez jouer",
+        Messages::ReasonLeadLow => "entamez petit et gardez vos cartes fortes",
+        Messages::ReasonLostTrick => {
+            "le pli est perdu pour vous, donnez votre carte la plus faible"
+        }
+        Messages::MistakeCost => "{} (environ {} points)",
+        Messages::MistakeBid => {
+            "Enchère {} alors que l'évaluation conseillait {} pour une main de {} points"
+        }
+        Messages::MistakeEcart => "L'écart{} laisse une main plus faible que celui du bot{}",
+        Messages::MistakePlay => "Joué{} alors que{} était meilleur",
+        Messages::MistakeLost => "Perdu{} contre {} au pli {}",
+        Messages::LogDeals => "{} distribue",
+        Messages::LogDealStarts => "Début de la donne {}",
+        Messages::LogBid => "{} : {}",
+        Messages::LogKingCalled => "{} appelle {}",
+        Messages::LogKitty => "Chien : {}",
+        Messages::LogHandle => "{} montre une {}",
+        Messages::LogCardPlayed => "{} joue {}",
+        Messages::LogTrickWon => "{} remporte le pli",
+        Messages::LogDealScored => "La {} est {} avec {} points",
+        Messages::ScreenDeal => "Donne {}",
+        Messages::ScreenTaker => " - {} par {}",
+        Messages::ScreenCalling => ", appel du {}",
+        Messages::ScreenTrickWinner => "{} remporte le pli",
+        Messages::ScreenHand => "Votre main :",
+        Messages::ScreenScores => "Scores",
+        Messages::ScreenAuction => "Enchères",
+        Messages::ScreenKitty => "Chien : {}",
+        Messages::ScreenEvents => "Événements",
+        Messages::ScreenHelp => "←/→ déplacer   Entrée choisir   ? conseil   q quitter",
+        Messages::ScreenNobodyBid => {
+            "Personne n'a pris. Entrée pour une nouvelle donne, q pour quitter"
+        }
+        Messages::ScreenNextDeal => "Entrée pour la donne suivante, q pour quitter",
+        Messages::ScreenIllegalCard => "Cette carte ne peut pas être jouée maintenant",
+        Messages::ServerWaiting => "En attente de {} joueur(s) sur {}",
+        Messages::ServerJoined => "{} rejoint la table",
+        Messages::ServerLeft => "{} a quitté la table, un bot joue jusqu'à son retour",
+        Messages::ServerGameOver => "La partie est terminée, merci d'avoir joué !",
+        Messages::DefaultName => "Invité",
+        Messages::ClientJoined => "Connecté à {} en tant que {}",
+        Messages::ClientClosed => "Le serveur a fermé la connexion",
+        Messages::NotInLobby => "Seule une action est attendue à cette table",
+        Messages::LobbyOpen => "Salon ouvert sur {}",
+        Messages::HttpOpen => "L'API HTTP écoute sur http://{}",
+        Messages::NoProfiles => "Aucune partie enregistrée dans {}",
+        Messages::UnknownProfile => "Aucun profil pour {}",
+        Messages::NoSuchTable => "Il n'y a pas de table {}",
+        Messages::TableFull => "La table {} n'a plus de place libre",
+        Messages::AlreadySeated => "Quittez d'abord votre table",
+        Messages::NotSeated => "Asseyez-vous d'abord à une table",
+        Messages::InvalidTable => "Une table a de 3 à 5 joueurs, pas plus de places pour des joueurs distants que de joueurs, et au moins une donne",
+        Messages::EarlyReveal => "Une table avec des joueurs distants ne montre les mains aux spectateurs qu'à la fin de la donne",
+        Messages::SessionRunning => "La session de la table n'est pas terminée",
+        Messages::SessionOver => "La session de la table {} est terminée, /restart pour rejouer",
+        Messages::SatDown => "{} s'assied à la table {}",
+        Messages::LeftTable => "{} quitte la table {}",
+        Messages::ClientSeated => "Assis à la table {} comme joueur {}",
+        Messages::TableLine => "Table {} : {} joueurs, {}/{} places prises, {} donne(s), {} spectateur(s), {} {}",
+        Messages::TableWaiting => "en attente",
+        Messages::TablePlaying => "en cours",
+        Messages::TableOver => "terminée",
+        Messages::NoTables => "Aucune table, /create <joueurs> <places> [donnes] en ouvre une",
+        Messages::LobbyHelp => "Commandes : /tables, /create <joueurs> <places> [donnes] [end|<plis>], /sit <table>, /leave, /restart, /say <texte>, /watch <table>",
+        Messages::UnknownCommand => "Commande inconnue '{}'",
+        Messages::Watching => "Vous regardez la table {}, /leave pour arrêter",
+        Messages::HandsOf => "Main de {} :",
+        Messages::ServerBack => "{} est de retour à la table",
+        Messages::NotYourTurn => "Attendez votre tour",
+        Messages::TableTaken => "Toutes les places de la table sont prises",
+        Messages::UnknownToken => "Jeton de session inconnu",
+        Messages::ClientToken => "Votre jeton de session est {}, rejoignez avec --token {} pour revenir",
+    }
+}
@@ -1,11 +1,25 @@
+pub mod action;
+pub mod arena;
 pub mod bid;
 pub mod card;
+pub mod cardset;
 pub mod deal;
+pub mod error;
+pub mod evaluation;
+pub mod event;
 pub mod game;
 pub mod hand;
+pub mod handle;
 pub mod kitty;
+pub mod locale;
+pub mod notation;
+pub mod phase;
 pub mod player;
+pub mod profile;
+pub mod render;
 pub mod score;
+pub mod simulation;
 pub mod taker;
 pub mod trick;
 pub mod utils;
+pub mod view;
@@ -0,0 +1,330 @@
+//! Portable Tarot Notation (PTN), a text format to exchange complete deals.
+//!
+//! A record starts with tag pairs, then lists the auction, the called king,
+//! the écart, the declared handles and the tricks. Cards are written with their `CardGetters::id`
+//! (`C14` is the King of Clubs, `T22` the Fool) and seats are indexes in the
+//! `Players` tag, starting at 0 with the first player to bid. Names are separated by
+//! commas, a comma or a backslash inside a name is escaped with a backslash.
+//! The `Seed` tag is only written for the simulated deals, which replay from it.
+//!
+//! ```text
+//! [Variant "4"]
+//! [Seed "42"]
+//! [Dealer "3"]
+//! [Players "Player 1, Player 2, Player 3, Player 4"]
+//! [Hand0 "C1 C2 C3 ..."]
+//! [Kitty "D4 T7 ..."]
+//!
+//! ; lines starting with a semicolon are comments
+//! Auction: 0:Passe 1:Garde 2:Passe 3:Passe
+//! King: H14
+//! Ecart: D2 D3 D5 H1 H2 S9
+//! Handles: 2:Simple
+//! 1. 0: C3 C5 C14 T2
+//! 2. 3: T21 T4 T9 T11
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use super::{
+    bid::Bids,
+    card::{Card, CardGetters},
+    handle::Handles,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum NotationError {
+    MalformedTag(String),
+    MalformedLine(String),
+    InvalidCard(String),
+    InvalidBid(String),
+    InvalidHandle(String),
+    InvalidSeat(String),
+    DuplicatedCard(String),
+    InvalidPlayers(usize),
+    MissingTag(&'static str),
+}
+impl Display for NotationError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Self::MalformedTag(tag) => write!(f, "Malformed tag: {}", tag),
+            Self::MalformedLine(line) => write!(f, "Malformed line: {}", line),
+            Self::InvalidCard(reason) => write!(f, "Invalid card: {}", reason),
+            Self::InvalidBid(reason) => write!(f, "Invalid bid: {}", reason),
+            Self::InvalidHandle(reason) => write!(f, "Invalid handle: {}", reason),
+            Self::InvalidSeat(seat) => write!(f, "Invalid seat: {}", seat),
+            Self::DuplicatedCard(id) => write!(f, "Card {} is dealt more than once", id),
+            Self::InvalidPlayers(n) => write!(f, "A deal is played by 3 to 5 players, not {}", n),
+            Self::MissingTag(tag) => write!(f, "Missing tag: {}", tag),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RecordedTrick {
+    pub leader: usize,
+    pub cards: Vec<Card>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DealRecord {
+    pub variant: usize,
+    // Set by the simulation, whose deals and bots replay from it
+    pub seed: Option<u64>,
+    pub dealer: Option<usize>,
+    pub players: Vec<String>,
+    pub hands: Vec<Vec<Card>>,
+    pub kitty: Vec<Card>,
+    pub auction: Vec<(usize, Bids)>,
+    pub called_king: Option<Card>,
+    pub ecart: Vec<Card>,
+    pub handles: Vec<(usize, Handles)>,
+    pub tricks: Vec<RecordedTrick>,
+}
+impl DealRecord {
+    pub fn new(players: Vec<String>) -> Self {
+        DealRecord {
+            variant: players.len(),
+            hands: vec![Vec::new(); players.len()],
+            players,
+            ..Default::default()
+        }
+    }
+    pub fn export(&self) -> String {
+        self.to_string()
+    }
+    pub fn import(text: &str) -> Result<Self, NotationError> {
+        text.parse()
+    }
+}
+
+impl Display for DealRecord {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        writeln!(f, "[Variant \"{}\"]", self.variant)?;
+        if let Some(seed) = self.seed {
+            writeln!(f, "[Seed \"{}\"]", seed)?;
+        }
+        if let Some(dealer) = self.dealer {
+            writeln!(f, "[Dealer \"{}\"]", dealer)?;
+        }
+        writeln!(f, "[Players \"{}\"]", format_names(&self.players))?;
+        for (seat, hand) in self.hands.iter().enumerate() {
+            writeln!(f, "[Hand{} \"{}\"]", seat, format_cards(hand))?;
+        }
+        writeln!(f, "[Kitty \"{}\"]", format_cards(&self.kitty))?;
+        writeln!(f)?;
+
+        let auction = self
+            .auction
+            .iter()
+            .map(|(seat, bid)| format!("{}:{}", seat, bid.id()))
+            .collect::<Vec<String>>();
+        writeln!(f, "Auction: {}", auction.join(" "))?;
+        if let Some(king) = self.called_king {
+            writeln!(f, "King: {}", king.id())?;
+        }
+        if !self.ecart.is_empty() {
+            writeln!(f, "Ecart: {}", format_cards(&self.ecart))?;
+        }
+        if !self.handles.is_empty() {
+            let handles = self
+                .handles
+                .iter()
+                .map(|(seat, handle)| format!("{}:{}", seat, handle))
+                .collect::<Vec<String>>();
+            writeln!(f, "Handles: {}", handles.join(" "))?;
+        }
+        for (index, trick) in self.tricks.iter().enumerate() {
+            writeln!(
+                f,
+                "{}. {}: {}",
+                index + 1,
+                trick.leader,
+                format_cards(&trick.cards)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DealRecord {
+    type Err = NotationError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut record = DealRecord::default();
+        let mut hands: Vec<(usize, Vec<Card>)> = Vec::new();
+        let mut has_players = false;
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            if line.starts_with('[') {
+                let (name, value) = parse_tag(line)?;
+                match name {
+                    "Variant" => record.variant = parse_number(value, line)?,
+                    "Seed" => record.seed = Some(parse_number(value, line)?),
+                    "Dealer" => record.dealer = Some(parse_number(value, line)?),
+                    "Players" => {
+                        has_players = true;
+                        record.players = parse_names(value);
+                    }
+                    "Kitty" => record.kitty = parse_cards(value)?,
+                    _ => match name.strip_prefix("Hand") {
+                        Some(seat) => hands.push((parse_number(seat, line)?, parse_cards(value)?)),
+                        None => return Err(NotationError::MalformedTag(line.to_string())),
+                    },
+                }
+                continue;
+            }
+
+            if let Some((number, trick)) = line.split_once(". ") {
+                if number.parse::<usize>().is_ok() {
+                    let (leader, cards) = trick
+                        .split_once(':')
+                        .ok_or_else(|| NotationError::MalformedLine(line.to_string()))?;
+                    record.tricks.push(RecordedTrick {
+                        leader: parse_number(leader, line)?,
+                        cards: parse_cards(cards)?,
+                    });
+                    continue;
+                }
+            }
+
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| NotationError::MalformedLine(line.to_string()))?;
+            match key.trim() {
+                "Auction" => {
+                    for entry in value.split_whitespace() {
+                        let (seat, bid) = entry
+                            .split_once(':')
+                            .ok_or_else(|| NotationError::InvalidBid(entry.to_string()))?;
+                        let bid = bid.parse::<Bids>().map_err(NotationError::InvalidBid)?;
+                        record.auction.push((parse_number(seat, entry)?, bid));
+                    }
+                }
+                "King" => record.called_king = Some(parse_card(value.trim())?),
+                "Ecart" => record.ecart = parse_cards(value)?,
+                "Handles" => {
+                    for entry in value.split_whitespace() {
+                        let (seat, handle) = entry
+                            .split_once(':')
+                            .ok_or_else(|| NotationError::InvalidHandle(entry.to_string()))?;
+                        let handle = handle
+                            .parse::<Handles>()
+                            .map_err(NotationError::InvalidHandle)?;
+                        record.handles.push((parse_number(seat, entry)?, handle));
+                    }
+                }
+                _ => return Err(NotationError::MalformedLine(line.to_string())),
+            }
+        }
+
+        if !has_players {
+            return Err(NotationError::MissingTag("Players"));
+        }
+        if record.variant == 0 {
+            record.variant = record.players.len();
+        }
+        record.hands = vec![Vec::new(); record.players.len()];
+        for (seat, cards) in hands {
+            match record.hands.get_mut(seat) {
+                Some(hand) => *hand = cards,
+                None => return Err(NotationError::InvalidSeat(seat.to_string())),
+            }
+        }
+        validate(&record)?;
+        Ok(record)
+    }
+}
+
+fn validate(record: &DealRecord) -> Result<(), NotationError> {
+    let n_players = record.players.len();
+    if !(3..=5).contains(&n_players) {
+        return Err(NotationError::InvalidPlayers(n_players));
+    }
+    let seats = record
+        .auction
+        .iter()
+        .map(|(seat, _)| *seat)
+        .chain(record.handles.iter().map(|(seat, _)| *seat))
+        .chain(record.tricks.iter().map(|trick| trick.leader))
+        .chain(record.dealer);
+    for seat in seats {
+        if seat >= n_players {
+            return Err(NotationError::InvalidSeat(seat.to_string()));
+        }
+    }
+
+    let mut dealt: Vec<Card> = Vec::new();
+    for card in record.hands.iter().flatten().chain(record.kitty.iter()) {
+        if dealt.contains(card) {
+            return Err(NotationError::DuplicatedCard(card.id()));
+        }
+        dealt.push(*card);
+    }
+    Ok(())
+}
+
+fn parse_tag(line: &str) -> Result<(&str, &str), NotationError> {
+    let malformed = || NotationError::MalformedTag(line.to_string());
+    let inner = line
+        .strip_prefix('[')
+        .and_then(|line| line.strip_suffix(']'))
+        .ok_or_else(malformed)?;
+    let (name, value) = inner.split_once(' ').ok_or_else(malformed)?;
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(malformed)?;
+    Ok((name, value))
+}
+
+fn parse_number<T: FromStr>(value: &str, context: &str) -> Result<T, NotationError> {
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| NotationError::MalformedLine(context.to_string()))
+}
+
+fn parse_card(id: &str) -> Result<Card, NotationError> {
+    id.parse::<Card>().map_err(NotationError::InvalidCard)
+}
+
+fn parse_cards(ids: &str) -> Result<Vec<Card>, NotationError> {
+    ids.split_whitespace().map(parse_card).collect()
+}
+
+fn format_names(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| name.replace('\\', "\\\\").replace(',', "\\,"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn parse_names(value: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut name = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => name.extend(chars.next()),
+            ',' => names.push(std::mem::take(&mut name).trim().to_string()),
+            _ => name.push(c),
+        }
+    }
+    names.push(name.trim().to_string());
+    names
+}
+
+fn format_cards(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|card| card.id())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -0,0 +1,21 @@
+use std::fmt::{Display, Formatter, Result};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum Phase {
+    #[default]
+    Dealing,
+    Auction,
+    KingCall,
+    Ecart,
+    Declarations,
+    Play,
+    Scoring,
+    Done,
+}
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
@@ -1,29 +1,53 @@
 use std::fmt::Display;
+use std::sync::{Arc, Mutex};
 
-use crate::common::{card::CardSuits, utils::select};
+use rand::{rngs::StdRng, thread_rng, SeedableRng};
+
+use crate::bots::{
+    advisor::advise,
+    beginner,
+    difficulty::Difficulties,
+    engine::Engine,
+    intermediate,
+    ismcts::{self, SearchConfig},
+};
+use crate::common::utils::{select_with_hint, HintSource, Lines};
 
 use super::{
+    action::Action,
     bid::{Bid, Bids},
-    card::{Card, KING_RANK},
+    card::Card,
+    error::TarotError,
     hand::Hand,
+    handle::Handles,
     kitty::{Kitty, KittyActions},
-    trick::{Trick, TrickActions},
-    utils::display,
+    locale::{text, Messages},
+    phase::Phase,
+    render::print_hand,
+    trick::TrickActions,
+    view::SeatView,
 };
 
 pub trait PlayerActions {
-    fn bid(&self, bid: &mut Bid) -> Bids;
-    fn call_king(&mut self) -> Card;
-    fn compose_kitty(&mut self, kitty: &mut Kitty) -> Vec<Card>;
-    fn play(&mut self, trick: &mut Trick);
+    fn act(&self, view: &SeatView) -> Result<Action, TarotError>;
+    fn bid(&self, view: &SeatView) -> Result<Bids, TarotError>;
+    fn call_king(&self, view: &SeatView) -> Result<Card, TarotError>;
+    fn discard(&self, view: &SeatView) -> Result<Card, TarotError>;
+    fn declare(&self, view: &SeatView) -> Result<Option<Handles>, TarotError>;
+    fn play(&self, view: &SeatView) -> Result<Card, TarotError>;
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct Player {
     pub id: u8,
     pub name: String,
-    score: u8,
     pub is_human: bool,
+    pub level: Difficulties,
+    pub search: SearchConfig,
+    // An external program deciding for this bot, shared by the clones of the player
+    pub engine: Option<Arc<Mutex<Engine>>>,
+    // Where a human player answers from, the terminal when unset
+    pub input: Option<Lines>,
     pub is_dealer: bool,
     pub cards: Vec<Card>,
     pub picked_up_cards: Vec<Card>,
@@ -42,56 +66,161 @@ impl Player {
             ..Default::default()
         }
     }
+
+    pub fn hint(&self, view: &SeatView) -> Option<String> {
+        advise(self, view).ok().map(|hint| hint.to_string())
+    }
 }
+// Players only decide from their own view of the deal, never from the full table
 impl PlayerActions for Player {
-    fn bid(&self, bid: &mut Bid) -> Bids {
+    fn act(&self, view: &SeatView) -> Result<Action, TarotError> {
+        if let (false, Some(engine)) = (self.is_human, &self.engine) {
+            let mut engine = engine
+                .lock()
+                .map_err(|_| TarotError::Protocol(String::from("The engine is unavailable")))?;
+            return engine.ask(view);
+        }
+        match (self.is_human, self.level, view.phase) {
+            (false, Difficulties::Beginner, _) => {
+                return match self.search.seed {
+                    Some(seed) => {
+                        beginner::act(view, &mut StdRng::seed_from_u64(seed ^ turn(view)))
+                    }
+                    None => beginner::act(view, &mut thread_rng()),
+                }
+            }
+            // The heuristic takes over in the unlikely case no hidden hands could be sampled
+            (false, Difficulties::Expert, Phase::Play) => {
+                let card = ismcts::search(view, &self.search).or_else(|_| self.play(view))?;
+                return Ok(Action::Play(card));
+            }
+            _ => {}
+        }
+        let action = match view.phase {
+            Phase::Auction => Action::Bid(self.bid(view)?),
+            Phase::KingCall => Action::CallKing(self.call_king(view)?),
+            Phase::Ecart => Action::Discard(self.discard(view)?),
+            Phase::Declarations if view.legal_actions.len() == 1 => Action::Declare(None),
+            Phase::Declarations => Action::Declare(self.declare(view)?),
+            _ => Action::Play(self.play(view)?),
+        };
+        Ok(action)
+    }
+    fn bid(&self, view: &SeatView) -> Result<Bids, TarotError> {
+        let mut bid = Bid::new(view.contract());
         if self.is_human {
-            bid.human_choose(&self.hand.cards)
+            bid.human_choose(&view.hand, Some(&|| self.hint(view)), self.input.as_ref())
         } else {
-            bid.bot_choose(&self.hand.cards)
+            Ok(bid.bot_choose(&view.hand, view.n_players()))
         }
     }
-    fn call_king(&mut self) -> Card {
-        let kings: [Card; 4] = [
-            Card::new(KING_RANK, CardSuits::Clubs),
-            Card::new(KING_RANK, CardSuits::Diamonds),
-            Card::new(KING_RANK, CardSuits::Hearts),
-            Card::new(KING_RANK, CardSuits::Spades),
-        ];
+    fn call_king(&self, view: &SeatView) -> Result<Card, TarotError> {
         if self.is_human {
-            human_call_king(&self.hand.cards, &kings)
+            let hint: HintSource = &|| self.hint(view);
+            human_call_king(&view.hand, &view.legal_cards(), hint, self.input.as_ref())
         } else {
-            bot_call_king(&self.hand.cards, &kings)
+            bot_call_king(&view.hand, &view.legal_cards())
         }
     }
-    fn compose_kitty(&mut self, kitty: &mut Kitty) -> Vec<Card> {
-        add_kitty_in_hand(&kitty.cards, &mut self.hand);
+    fn discard(&self, view: &SeatView) -> Result<Card, TarotError> {
+        let kitty = Kitty {
+            cards: view.ecart.clone(),
+            ..Kitty::new(view.n_players())
+        };
         if self.is_human {
-            kitty.human_compose(&mut self.hand.cards)
+            let hint: HintSource = &|| self.hint(view);
+            kitty.human_discard(
+                &view.hand,
+                &view.legal_cards(),
+                Some(hint),
+                self.input.as_ref(),
+            )
         } else {
-            kitty.bot_compose(&self.hand.cards)
+            kitty.bot_discard(&view.hand, &view.legal_cards())
         }
     }
-    fn play(&mut self, trick: &mut Trick) {
+    fn declare(&self, view: &SeatView) -> Result<Option<Handles>, TarotError> {
+        let handles = view.legal_handles();
         if self.is_human {
-            trick.human_play(&mut self.hand.cards)
+            human_declare(
+                &view.hand,
+                &handles,
+                &|| self.hint(view),
+                self.input.as_ref(),
+            )
         } else {
-            trick.bot_play(&mut self.hand.cards)
+            Ok(handles.last().copied())
         }
     }
+    fn play(&self, view: &SeatView) -> Result<Card, TarotError> {
+        if self.is_human {
+            let hint: HintSource = &|| self.hint(view);
+            view.trick.human_play(
+                &view.hand,
+                &view.legal_cards(),
+                Some(hint),
+                self.input.as_ref(),
+            )
+        } else {
+            intermediate::play(view)
+        }
+    }
+}
+
+// Each decision of a seat happens with a different number of cards in hand
+fn turn(view: &SeatView) -> u64 {
+    (view.seat as u64) << 32 | (view.hand.len() as u64) << 8 | view.phase as u64
+}
+
+// Calling a king held by the bot would make it play alone
+fn bot_call_king(cards: &[Card], kings: &[Card]) -> Result<Card, TarotError> {
+    kings
+        .iter()
+        .find(|king| !cards.contains(king))
+        .or(kings.first())
+        .copied()
+        .ok_or(TarotError::NoAvailableChoice)
 }
 
-fn bot_call_king(_cards: &[Card], _kings: &[Card]) -> Card {
-    todo!()
+fn human_call_king(
+    cards: &[Card],
+    kings: &[Card],
+    hint: HintSource,
+    input: Option<&Lines>,
+) -> Result<Card, TarotError> {
+    println!("\n{}", text(Messages::YourCards));
+    print_hand(cards, &[]);
+    select_with_hint(
+        Some(text(Messages::WhichKing)),
+        Some(kings.to_vec()),
+        Some(hint),
+        input,
+    )
 }
 
-fn human_call_king(cards: &[Card], kings: &[Card]) -> Card {
-    println!("\nYour cards:");
-    display(cards);
-    select(Some("Which king do you call?"), Some(kings.to_vec())).unwrap()
+fn human_declare(
+    cards: &[Card],
+    handles: &[Handles],
+    hint: HintSource,
+    input: Option<&Lines>,
+) -> Result<Option<Handles>, TarotError> {
+    println!("\n{}", text(Messages::YourCards));
+    print_hand(cards, &[]);
+    let mut choices: Vec<&str> = handles.iter().map(|handle| handle.label()).collect();
+    choices.push(text(Messages::NoHandle));
+    let choice = select_with_hint(
+        Some(text(Messages::DeclareHandle)),
+        Some(choices),
+        Some(hint),
+        input,
+    )?;
+    Ok(handles
+        .iter()
+        .find(|handle| handle.label() == choice)
+        .copied())
 }
 
-fn add_kitty_in_hand(kitty: &[Card], hand: &mut Hand) {
+pub fn add_kitty_in_hand(kitty: &[Card], hand: &mut Hand) {
     let mut cards = hand.cards.to_vec();
     cards.extend_from_slice(kitty);
     cards.sort_unstable_by_key(|card| (card.suit.initial, card.rank));
@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    bid::Bids,
+    card::CardGetters,
+    deal::Deal,
+    error::TarotError,
+    hand::Side,
+    player::Player,
+    simulation::{ratio, ContractStats},
+};
+
+const PROFILES_VARIABLE: &str = "TAROT_PROFILES";
+const PROFILES_FILE: &str = "profiles.json";
+
+// Where the deals of the human players are recorded, nothing is recorded until it is set.
+// The lock also keeps the tables of a lobby from writing the file at the same time
+static PROFILES: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+pub fn set_profiles(path: Option<PathBuf>) {
+    if let Ok(mut profiles) = PROFILES.lock() {
+        *profiles = path;
+    }
+}
+
+// The TAROT_PROFILES variable, else a file in the home directory
+pub fn default_profiles() -> PathBuf {
+    if let Some(path) = std::env::var_os(PROFILES_VARIABLE) {
+        return PathBuf::from(path);
+    }
+    match std::env::var_os("HOME") {
+        Some(home) => Path::new(&home).join(".tarot-cli").join(PROFILES_FILE),
+        None => PathBuf::from(PROFILES_FILE),
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub games: usize,
+    pub deals: usize,
+    pub points: f64,
+    // Same order as Bids::AVAILABLE_BIDS
+    pub contracts: [ContractStats; 4],
+    pub taker_points: f64,
+    // The partner of the taker is neither a taker nor a defender
+    pub defended: usize,
+    pub defender_points: f64,
+    pub petits_au_bout: usize,
+    pub handles: usize,
+    pub slams: usize,
+}
+impl Profile {
+    pub fn contract(&self, bid: Bids) -> ContractStats {
+        Bids::AVAILABLE_BIDS
+            .iter()
+            .position(|available| *available == bid)
+            .map(|index| self.contracts[index])
+            .unwrap_or_default()
+    }
+
+    pub fn taken(&self) -> usize {
+        self.contracts.iter().map(|contract| contract.taken).sum()
+    }
+
+    pub fn average_as_taker(&self) -> f64 {
+        ratio(self.taker_points, self.taken())
+    }
+
+    pub fn average_as_defender(&self) -> f64 {
+        ratio(self.defender_points, self.defended)
+    }
+
+    fn record(&mut self, deal: &Deal, seat: usize) {
+        let Some(score) = &deal.score else {
+            return;
+        };
+        let points = score.scores.get(seat).copied().unwrap_or_default();
+        let side = deal.side(seat);
+        self.deals += 1;
+        self.points += points;
+        if deal.taker.as_ref().is_some_and(|taker| taker.seat == seat) {
+            if let Some(index) = Bids::AVAILABLE_BIDS
+                .iter()
+                .position(|bid| *bid == score.bid)
+            {
+                self.contracts[index].taken += 1;
+                self.contracts[index].won += score.contract_won as usize;
+            }
+            self.taker_points += points;
+        } else if side == Side::Defense {
+            self.defended += 1;
+            self.defender_points += points;
+        }
+        // The Petit au bout goes to the player who played it in the last trick
+        let petit = deal.tricks.last().and_then(|trick| {
+            let index = trick.played_cards.iter().position(|card| card.is_petit())?;
+            Some((trick.leader + index) % deal.players.len())
+        });
+        if score.petit_au_bout == Some(side) && petit == Some(seat) {
+            self.petits_au_bout += 1;
+        }
+        self.handles += deal.handles.iter().filter(|h| h.seat == seat).count();
+        self.slams += (score.slam == Some(side)) as usize;
+    }
+}
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        writeln!(
+            f,
+            "{} games, {} deals, {:.1} points",
+            self.games, self.deals, self.points
+        )?;
+        writeln!(f, "{:<12}{:>8}{:>9}", "Contract", "Taken", "Success")?;
+        for bid in Bids::AVAILABLE_BIDS {
+            let contract = self.contract(bid);
+            writeln!(
+                f,
+                "{:<12}{:>8}{:>8.1}%",
+                bid.to_string(),
+                contract.taken,
+                100.0 * ratio(contract.won as f64, contract.taken)
+            )?;
+        }
+        writeln!(
+            f,
+            "Average as taker: {:.2}, as defender: {:.2}",
+            self.average_as_taker(),
+            self.average_as_defender()
+        )?;
+        writeln!(
+            f,
+            "Petits au bout: {}, handles: {}, slams: {}",
+            self.petits_au_bout, self.handles, self.slams
+        )
+    }
+}
+
+// The lifetime statistics of the players, by name
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profiles {
+    pub players: BTreeMap<String, Profile>,
+}
+impl Profiles {
+    // A missing file is an empty season
+    pub fn load(path: &Path) -> std::result::Result<Self, TarotError> {
+        match fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Profiles::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::result::Result<(), TarotError> {
+        if let Some(directory) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    // Only the human players of a scored deal get it in their profile
+    pub fn record_deal(&mut self, deal: &Deal) {
+        for (seat, player) in deal.players.iter().enumerate() {
+            if player.is_human {
+                let profile = self.players.entry(player.name.clone()).or_default();
+                profile.record(deal, seat);
+            }
+        }
+    }
+
+    pub fn record_game(&mut self, names: &[String]) {
+        for name in names {
+            self.players.entry(name.clone()).or_default().games += 1;
+        }
+    }
+
+    // The best total first
+    pub fn leaderboard(&self) -> Vec<(&String, &Profile)> {
+        let mut players: Vec<_> = self.players.iter().collect();
+        players.sort_by(|(_, a), (_, b)| b.points.total_cmp(&a.points));
+        players
+    }
+}
+impl Display for Profiles {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        writeln!(
+            f,
+            "{:<16}{:>7}{:>7}{:>10}{:>9}{:>9}",
+            "Player", "Games", "Deals", "Points", "Taker", "Defender"
+        )?;
+        let leaderboard = self.leaderboard();
+        for (name, profile) in &leaderboard {
+            writeln!(
+                f,
+                "{:<16}{:>7}{:>7}{:>10.1}{:>9.2}{:>9.2}",
+                name,
+                profile.games,
+                profile.deals,
+                profile.points,
+                profile.average_as_taker(),
+                profile.average_as_defender()
+            )?;
+        }
+        for (name, profile) in leaderboard {
+            write!(f, "\n{}\n{}", name, profile)?;
+        }
+        Ok(())
+    }
+}
+
+// Changes the recorded profiles, if any
+fn update(change: impl FnOnce(&mut Profiles)) -> std::result::Result<(), TarotError> {
+    let Ok(path) = PROFILES.lock() else {
+        return Ok(());
+    };
+    let Some(path) = path.as_ref() else {
+        return Ok(());
+    };
+    let mut profiles = Profiles::load(path)?;
+    change(&mut profiles);
+    profiles.save(path)
+}
+
+// A profile that cannot be saved does not stop the game
+pub fn record_deal(deal: &Deal) {
+    if let Err(error) = update(|profiles| profiles.record_deal(deal)) {
+        eprintln!("{}", error);
+    }
+}
+
+pub fn humans(players: &[Player]) -> Vec<String> {
+    players
+        .iter()
+        .filter(|player| player.is_human)
+        .map(|player| player.name.clone())
+        .collect()
+}
+
+pub fn record_game(names: &[String]) {
+    if let Err(error) = update(|profiles| profiles.record_game(names)) {
+        eprintln!("{}", error);
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossterm::style::{Attribute, Color, Stylize};
+
+use super::{
+    card::{Card, CardGetters, CardSuits, JACK_RANK, KING_RANK, KNIGHT_RANK, QUEEN_RANK},
+    locale::{text, Messages},
+};
+
+const DEFAULT_WIDTH: usize = 80;
+const SUIT_COLUMN: usize = 10;
+const SEPARATOR: &str = "  ";
+
+static COLOR: AtomicBool = AtomicBool::new(true);
+
+pub fn set_color(enabled: bool) {
+    COLOR.store(enabled, Ordering::Relaxed);
+}
+
+pub fn color() -> bool {
+    COLOR.load(Ordering::Relaxed)
+}
+
+// Dumb terminals and the NO_COLOR convention disable the escape codes
+pub fn terminal_supports_color() -> bool {
+    std::env::var_os("NO_COLOR").is_none()
+        && std::env::var("TERM").map_or(true, |term| term != "dumb")
+}
+
+pub fn terminal_width() -> usize {
+    crossterm::terminal::size().map_or(DEFAULT_WIDTH, |(width, _)| width as usize)
+}
+
+// The hand grouped by suit, trumps last
+pub fn sorted(hand: &[Card]) -> Vec<Card> {
+    let mut cards = hand.to_vec();
+    cards.sort_by_key(|card| (suit_order(card.suit.name), card.rank));
+    cards
+}
+
+fn suit_order(suit: CardSuits) -> usize {
+    CardSuits::AVAILABLE_SUITS
+        .iter()
+        .position(|available| *available == suit)
+        .unwrap_or_default()
+}
+
+pub fn label(card: &Card) -> String {
+    if card.is_fool() {
+        return String::from(text(Messages::Fool));
+    }
+    let rank = match (card.rank, card.suit.name) {
+        (_, CardSuits::Trumps) => card.rank.to_string(),
+        (JACK_RANK, _) => String::from(text(Messages::JackInitial)),
+        (KNIGHT_RANK, _) => String::from(text(Messages::KnightInitial)),
+        (QUEEN_RANK, _) => String::from(text(Messages::QueenInitial)),
+        (KING_RANK, _) => String::from(text(Messages::KingInitial)),
+        (rank, _) => rank.to_string(),
+    };
+    format!("{}{}", rank, card.suit.icon)
+}
+
+pub fn labels(cards: &[Card]) -> String {
+    cards.iter().map(label).collect::<Vec<_>>().join(" ")
+}
+
+// Red suits in red, trumps in blue and oudlers in bold yellow
+pub fn paint(card: &Card, color: bool) -> String {
+    let text = label(card);
+    if !color {
+        return text;
+    }
+    let styled = match card.suit.name {
+        _ if card.is_oudler() => text.with(Color::Yellow).attribute(Attribute::Bold),
+        CardSuits::Hearts | CardSuits::Diamonds => text.with(Color::Red),
+        CardSuits::Trumps => text.with(Color::Blue),
+        CardSuits::Clubs | CardSuits::Spades => text.stylize(),
+    };
+    styled.to_string()
+}
+
+pub fn paint_all(cards: &[Card], color: bool) -> String {
+    cards
+        .iter()
+        .map(|card| paint(card, color))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// The legal cards in the order they are numbered by render_hand
+pub fn numbered(legal: &[Card]) -> Vec<Card> {
+    sorted(legal)
+}
+
+// One line per suit, wrapped to the width. Only the legal cards get a number to choose them,
+// the others are dimmed. Without legal cards, the hand is only shown
+pub fn render_hand(hand: &[Card], legal: &[Card], color: bool, width: usize) -> String {
+    let numbers = numbered(legal);
+    let mut lines = Vec::new();
+    for suit in CardSuits::AVAILABLE_SUITS {
+        let cards: Vec<Card> = sorted(hand)
+            .into_iter()
+            .filter(|card| card.suit.name == suit)
+            .collect();
+        if cards.is_empty() {
+            continue;
+        }
+        let mut line = format!("{:<1$}", suit.label(), SUIT_COLUMN);
+        let mut length = SUIT_COLUMN;
+        for card in cards {
+            let (number, painted) = match numbers.iter().position(|legal| *legal == card) {
+                Some(index) => (format!("{}:", index), paint(&card, color)),
+                None if color && !legal.is_empty() => (
+                    String::new(),
+                    label(&card).attribute(Attribute::Dim).to_string(),
+                ),
+                None => (String::new(), paint(&card, color)),
+            };
+            let entry_length = number.chars().count() + label(&card).chars().count();
+            if length > SUIT_COLUMN && length + SEPARATOR.len() + entry_length > width {
+                lines.push(line);
+                line = " ".repeat(SUIT_COLUMN);
+                length = SUIT_COLUMN;
+            }
+            if length > SUIT_COLUMN {
+                line += SEPARATOR;
+                length += SEPARATOR.len();
+            }
+            line += &format!("{}{}", number, painted);
+            length += entry_length;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+pub fn print_hand(hand: &[Card], legal: &[Card]) {
+    println!("{}", render_hand(hand, legal, color(), terminal_width()));
+}
@@ -1,8 +1,39 @@
+use serde::{Deserialize, Serialize};
+
 use super::{
     bid::Bids,
     card::{Card, CardGetters},
+    hand::Side,
+    handle::Handle,
 };
 
+pub const CONTRACT_POINTS: f64 = 25.0;
+pub const PETIT_AU_BOUT_POINTS: f64 = 10.0;
+const SLAM_POINTS: f64 = 200.0;
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DealScore {
+    pub bid: Bids,
+    pub taker_points: f64,
+    pub needed_points: f64,
+    pub oudlers: usize,
+    pub contract_won: bool,
+    pub petit_au_bout: Option<Side>,
+    pub handle_bonus: f64,
+    pub slam: Option<Side>,
+    pub scores: Vec<f64>,
+}
+
+pub struct ScoreInput<'a> {
+    pub bid: Bids,
+    pub attack_cards: &'a [Card],
+    pub sides: &'a [Side],
+    pub taker: usize,
+    pub petit_au_bout: Option<Side>,
+    pub handles: &'a [Handle],
+    pub slam: Option<Side>,
+}
+
 pub fn compute_oudlers(cards: &[Card]) -> usize {
     cards.iter().filter(|card| card.is_oudler()).count()
 }
@@ -15,12 +46,6 @@ fn compute_needed_points(cards: &[Card]) -> f64 {
     get_needed_points(compute_oudlers(cards))
 }
 
-fn diff_points(cards: &[Card]) -> f64 {
-    let points = compute_points(cards);
-    let needed_points = compute_needed_points(cards);
-    points - needed_points
-}
-
 fn get_needed_points(n_oudlers: usize) -> f64 {
     match n_oudlers {
         0 => 56.0,
@@ -31,7 +56,7 @@ fn get_needed_points(n_oudlers: usize) -> f64 {
     }
 }
 
-fn multiplier(bid: &Bids) -> f64 {
+pub fn multiplier(bid: &Bids) -> f64 {
     match bid {
         Bids::Petite => 1.0,
         Bids::Garde => 2.0,
@@ -41,10 +66,56 @@ fn multiplier(bid: &Bids) -> f64 {
     }
 }
 
-// fn compute_score(hand: &Hand) -> f64 {
-//     let points = diff_points(&hand.attack_pool);
-//     let petit_au_bout = if hand.bonus_petit_au_bout { 10.0 } else { 0.0 };
-//     ((25.0 + points + petit_au_bout) * multiplier(hand.bid))
-//         + hand.bonus_poignee
-//         + hand.bonus_chelem
-// }
+pub fn compute_deal_score(input: &ScoreInput) -> DealScore {
+    let taker_points = compute_points(input.attack_cards);
+    let needed_points = compute_needed_points(input.attack_cards);
+    let contract_won = taker_points >= needed_points;
+    let multiplier = multiplier(&input.bid);
+    let sign = |side: Side| if side == Side::Attack { 1.0 } else { -1.0 };
+
+    let contract = if contract_won { 1.0 } else { -1.0 }
+        * (CONTRACT_POINTS + (taker_points - needed_points).abs())
+        * multiplier;
+    let petit_au_bout = input
+        .petit_au_bout
+        .map_or(0.0, |side| sign(side) * PETIT_AU_BOUT_POINTS * multiplier);
+    // Handles always go to the side winning the contract
+    let handle_bonus = input
+        .handles
+        .iter()
+        .fold(0.0, |acc, handle| acc + handle.kind.bonus());
+    let handle = if contract_won { 1.0 } else { -1.0 } * handle_bonus;
+    let slam = input.slam.map_or(0.0, |side| sign(side) * SLAM_POINTS);
+    let attack_gain = contract + petit_au_bout + handle + slam;
+
+    let n_attackers = input
+        .sides
+        .iter()
+        .filter(|side| **side == Side::Attack)
+        .count();
+    let n_defenders = input.sides.len() - n_attackers;
+    let scores = input
+        .sides
+        .iter()
+        .enumerate()
+        .map(|(seat, side)| match side {
+            Side::Defense => -attack_gain,
+            Side::Attack if seat == input.taker => {
+                attack_gain * (n_defenders as f64 - (n_attackers as f64 - 1.0))
+            }
+            Side::Attack => attack_gain,
+        })
+        .collect();
+
+    DealScore {
+        bid: input.bid,
+        taker_points,
+        needed_points,
+        oudlers: compute_oudlers(input.attack_cards),
+        contract_won,
+        petit_au_bout: input.petit_au_bout,
+        handle_bonus,
+        slam: input.slam,
+        scores,
+    }
+}
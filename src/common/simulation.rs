@@ -0,0 +1,297 @@
+use std::cell::Cell;
+use std::fmt::{Display, Formatter, Result};
+use std::panic::{catch_unwind, set_hook, take_hook, AssertUnwindSafe};
+use std::sync::Once;
+use std::thread;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::bots::{difficulty::Difficulties, ismcts::SearchConfig};
+
+use super::{
+    bid::Bids,
+    deal::{Deal, DealActions},
+    error::TarotError,
+    game::new_deck,
+    player::Player,
+    score::DealScore,
+};
+
+pub const DEFAULT_DEALS: usize = 1000;
+pub const DEFAULT_PLAYERS: u8 = 4;
+
+static QUIET_HOOK: Once = Once::new();
+
+thread_local! {
+    // Set while the thread plays a deal, whose panics are reported with its seed
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationConfig {
+    pub deals: usize,
+    pub threads: usize,
+    pub n_players: u8,
+    // Levels by seat, the missing seats are intermediate bots
+    pub levels: Vec<Difficulties>,
+    // Seed of the first deal, the bots get theirs from the seed of each deal
+    pub seed: Option<u64>,
+    pub search: SearchConfig,
+}
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        SimulationConfig {
+            deals: DEFAULT_DEALS,
+            threads: 1,
+            n_players: DEFAULT_PLAYERS,
+            levels: Vec::new(),
+            seed: None,
+            search: SearchConfig::default(),
+        }
+    }
+}
+impl SimulationConfig {
+    pub fn level(&self, seat: usize) -> Difficulties {
+        self.levels.get(seat).copied().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Passed,
+    Scored(DealScore),
+    Failed(String),
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractStats {
+    pub taken: usize,
+    pub won: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Statistics {
+    pub seed: u64,
+    pub players: Vec<String>,
+    pub deals: usize,
+    pub passed: usize,
+    // Same order as Bids::AVAILABLE_BIDS
+    pub contracts: [ContractStats; 4],
+    pub petit_au_bout: usize,
+    pub scores: Vec<f64>,
+    // Seed of the failing deal, to replay it alone
+    pub failures: Vec<(u64, String)>,
+}
+impl Statistics {
+    pub fn record(&mut self, seed: u64, outcome: Outcome) {
+        self.deals += 1;
+        match outcome {
+            Outcome::Passed => self.passed += 1,
+            Outcome::Failed(reason) => self.failures.push((seed, reason)),
+            Outcome::Scored(score) => {
+                if let Some(index) = Bids::AVAILABLE_BIDS
+                    .iter()
+                    .position(|bid| *bid == score.bid)
+                {
+                    self.contracts[index].taken += 1;
+                    self.contracts[index].won += score.contract_won as usize;
+                }
+                self.petit_au_bout += score.petit_au_bout.is_some() as usize;
+                self.scores
+                    .resize(score.scores.len().max(self.scores.len()), 0.0);
+                for (total, score) in self.scores.iter_mut().zip(score.scores) {
+                    *total += score;
+                }
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: Statistics) {
+        self.deals += other.deals;
+        self.passed += other.passed;
+        for (contract, other) in self.contracts.iter_mut().zip(other.contracts) {
+            contract.taken += other.taken;
+            contract.won += other.won;
+        }
+        self.petit_au_bout += other.petit_au_bout;
+        self.scores
+            .resize(other.scores.len().max(self.scores.len()), 0.0);
+        for (total, score) in self.scores.iter_mut().zip(other.scores) {
+            *total += score;
+        }
+        self.failures.extend(other.failures);
+    }
+
+    pub fn contract(&self, bid: Bids) -> ContractStats {
+        Bids::AVAILABLE_BIDS
+            .iter()
+            .position(|available| *available == bid)
+            .map(|index| self.contracts[index])
+            .unwrap_or_default()
+    }
+
+    pub fn scored(&self) -> usize {
+        self.contracts.iter().map(|contract| contract.taken).sum()
+    }
+
+    pub fn average_score(&self, seat: usize) -> f64 {
+        ratio(
+            self.scores.get(seat).copied().unwrap_or_default(),
+            self.scored(),
+        )
+    }
+}
+impl Display for Statistics {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        writeln!(f, "Simulated {} deals with seed {}", self.deals, self.seed)?;
+        writeln!(
+            f,
+            "Nobody bid: {} ({:.1}%)",
+            self.passed,
+            100.0 * ratio(self.passed as f64, self.deals)
+        )?;
+        writeln!(
+            f,
+            "\n{:<12}{:>8}{:>8}{:>9}",
+            "Contract", "Taken", "Share", "Success"
+        )?;
+        for bid in Bids::AVAILABLE_BIDS {
+            let contract = self.contract(bid);
+            writeln!(
+                f,
+                "{:<12}{:>8}{:>7.1}%{:>8.1}%",
+                bid.to_string(),
+                contract.taken,
+                100.0 * ratio(contract.taken as f64, self.deals),
+                100.0 * ratio(contract.won as f64, contract.taken)
+            )?;
+        }
+        writeln!(
+            f,
+            "\nPetit au bout: {} ({:.1}% of the played deals)",
+            self.petit_au_bout,
+            100.0 * ratio(self.petit_au_bout as f64, self.scored())
+        )?;
+        writeln!(f, "\nAverage score by deal:")?;
+        for (seat, name) in self.players.iter().enumerate() {
+            writeln!(f, "{}: {:.2}", name, self.average_score(seat))?;
+        }
+        writeln!(f, "\nFailures: {}", self.failures.len())?;
+        for (seed, reason) in &self.failures {
+            writeln!(f, "Deal with seed {}: {}", seed, reason)?;
+        }
+        Ok(())
+    }
+}
+
+// Every deal gets its own seed, so the results do not depend on the number of threads
+pub fn simulate(config: &SimulationConfig) -> Statistics {
+    silence_deal_panics();
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let threads = config.threads.clamp(1, config.deals.max(1));
+    let statistics = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| {
+                scope.spawn(move || {
+                    let mut statistics = Statistics::default();
+                    for index in (worker..config.deals).step_by(threads) {
+                        let deal_seed = seed.wrapping_add(index as u64);
+                        statistics.record(deal_seed, play_deal(config, deal_seed));
+                    }
+                    statistics
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap_or_default())
+            .fold(Statistics::default(), |mut all, statistics| {
+                all.merge(statistics);
+                all
+            })
+    });
+    let mut failures = statistics.failures;
+    failures.sort_by_key(|(seed, _)| *seed);
+    Statistics {
+        seed,
+        failures,
+        players: (0..config.n_players as usize)
+            .map(|seat| format!("Player {} ({})", seat + 1, config.level(seat)))
+            .collect(),
+        ..statistics
+    }
+}
+
+// A panic in the engine or the bots is recorded as a failure instead of stopping everything
+pub fn play_deal(config: &SimulationConfig, seed: u64) -> Outcome {
+    QUIET.set(true);
+    let outcome = catch_unwind(AssertUnwindSafe(|| run_deal(config, seed)));
+    QUIET.set(false);
+    match outcome {
+        Ok(Ok(Some(score))) => Outcome::Scored(score),
+        Ok(Ok(None)) => Outcome::Passed,
+        Ok(Err(error)) => Outcome::Failed(error.to_string()),
+        Err(panic) => Outcome::Failed(
+            panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("The deal panicked")),
+        ),
+    }
+}
+
+// Wraps the panic hook once, for the whole process: the threads playing a deal keep quiet,
+// the others still get the hook that was in place
+pub fn silence_deal_panics() {
+    QUIET_HOOK.call_once(|| {
+        let previous = take_hook();
+        set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                previous(info)
+            }
+        }));
+    });
+}
+
+fn run_deal(
+    config: &SimulationConfig,
+    seed: u64,
+) -> std::result::Result<Option<DealScore>, TarotError> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut deck = new_deck();
+    deck.shuffle(&mut rng);
+    let mut players: Vec<Player> = (0..config.n_players as usize)
+        .map(|seat| {
+            let mut player = Player::new(format!("Player {}", seat + 1), seat as u8 + 1);
+            player.level = config.level(seat);
+            player.is_dealer = seat + 1 == config.n_players as usize;
+            player.search = SearchConfig {
+                seed: Some(rng.gen()),
+                ..config.search
+            };
+            player
+        })
+        .collect();
+
+    let mut deal = Deal::deal_with(&mut players, &mut deck, &mut rng)?;
+    deal.record.seed = Some(seed);
+    deal.take_bids()?;
+    if deal.taker.is_none() {
+        return Ok(None);
+    }
+    deal.call_king()?;
+    deal.compose_kitty()?;
+    deal.declare_handles()?;
+    deal.play_tricks()?;
+    deal.compute_score()?;
+    Ok(deal.score)
+}
+
+pub fn ratio(value: f64, total: usize) -> f64 {
+    match total {
+        0 => 0.0,
+        _ => value / total as f64,
+    }
+}
@@ -1,7 +1,9 @@
-use super::{bid::Bids, player::Player};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default)]
+use super::bid::Bids;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Taker {
-    pub player: Player,
+    pub seat: usize,
     pub bid: Bids,
 }
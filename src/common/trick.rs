@@ -1,20 +1,46 @@
-use crate::common::utils::{display, select};
+use serde::{Deserialize, Serialize};
 
-use super::card::{Card, CardActions, CardSuits, CardSuitsGetters};
+use crate::common::{
+    locale::{fill, text, Messages},
+    render::{color, paint_all},
+    utils::{select_card, HintSource, Lines},
+};
+
+use super::{
+    action::Action,
+    card::{Card, CardActions, CardGetters, CardSuits, CardSuitsGetters},
+    error::TarotError,
+};
 
 pub trait TrickActions {
     fn get_best_played_card_index(&self, played_suit: Option<CardSuits>) -> Option<usize>;
-    fn human_play(&mut self, cards: &mut Vec<Card>);
-    fn bot_play(&mut self, cards: &mut Vec<Card>);
+    fn human_play(
+        &self,
+        cards: &[Card],
+        allowed_cards: &[Card],
+        hint: Option<HintSource>,
+        input: Option<&Lines>,
+    ) -> Result<Card, TarotError>;
+    fn bot_play(&self, cards: &[Card], allowed_cards: &[Card]) -> Result<Card, TarotError>;
 }
 
 pub trait TrickGetters {
     fn played_suit(&self) -> Option<CardSuits>;
+    fn winner(&self, n_players: usize) -> Option<usize>;
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Trick {
     pub played_cards: Vec<Card>,
+    pub leader: usize,
+}
+impl Trick {
+    pub fn new(leader: usize) -> Self {
+        Trick {
+            leader,
+            ..Default::default()
+        }
+    }
 }
 
 impl TrickActions for Trick {
@@ -22,51 +48,57 @@ impl TrickActions for Trick {
         if self.played_cards.is_empty() {
             return None;
         }
-        let mut best_card_index = 0;
         let played_suit = played_suit.or_else(|| self.played_suit());
+        let mut best_card_index: Option<usize> = None;
 
         for (index, card) in self.played_cards.iter().enumerate() {
-            if card.is_superior_than(&self.played_cards[best_card_index], Some(played_suit?)) {
-                best_card_index = index;
+            // The Fool never wins a trick
+            if card.is_fool() {
+                continue;
+            }
+            match best_card_index {
+                Some(best_index)
+                    if !card.is_superior_than(&self.played_cards[best_index], played_suit) => {}
+                _ => best_card_index = Some(index),
             }
         }
 
-        Some(best_card_index)
+        best_card_index.or(Some(0))
     }
 
-    fn human_play(&mut self, cards: &mut Vec<Card>) {
-        println!("\nYour cards:");
-        display(cards);
-        let card = select(
-            Some("Which card do you play?"),
-            Some(self.played_cards.to_vec()),
-        )
-        .unwrap();
-
-        if check_selected_card(self, cards, &card).is_err() {
-            self.human_play(cards);
+    fn human_play(
+        &self,
+        cards: &[Card],
+        allowed_cards: &[Card],
+        hint: Option<HintSource>,
+        input: Option<&Lines>,
+    ) -> Result<Card, TarotError> {
+        if !self.played_cards.is_empty() {
+            let played = paint_all(&self.played_cards, color());
+            println!("\n{}", fill(Messages::TrickContains, &[&played]));
         }
-
-        let index = cards
-            .iter()
-            .position(|c| c.suit.name == card.suit.name && c.rank == card.rank)
-            .unwrap();
-        cards.remove(index);
-
-        self.played_cards.push(card);
+        select_card(text(Messages::WhichCard), cards, allowed_cards, hint, input)
     }
 
-    fn bot_play(&mut self, _cards: &mut Vec<Card>) {
-        todo!("Implement bot play")
+    fn bot_play(&self, _cards: &[Card], allowed_cards: &[Card]) -> Result<Card, TarotError> {
+        allowed_cards
+            .iter()
+            .min_by(|a, b| a.score().total_cmp(&b.score()).then(a.rank.cmp(&b.rank)))
+            .copied()
+            .ok_or(TarotError::NoAvailableChoice)
     }
 }
 
 impl TrickGetters for Trick {
     fn played_suit(&self) -> Option<CardSuits> {
-        if self.played_cards.is_empty() {
-            return None;
-        }
-        Some(self.played_cards[0].suit.name)
+        self.played_cards
+            .iter()
+            .find(|card| !card.is_fool())
+            .map(|card| card.suit.name)
+    }
+    fn winner(&self, n_players: usize) -> Option<usize> {
+        let best_index = self.get_best_played_card_index(self.played_suit())?;
+        Some((self.leader + best_index) % n_players)
     }
 }
 
@@ -74,77 +106,62 @@ pub fn check_selected_card(
     trick: &Trick,
     player_cards: &[Card],
     player_selected_card: &Card,
-) -> Result<bool, &'static str> {
+) -> Result<(), TarotError> {
     let allowed_cards = allowed_cards_to_play(trick, player_cards);
     if allowed_cards.contains(player_selected_card) {
-        Ok(true)
+        Ok(())
     } else {
-        Err("Selected card is not allowed to be played")
+        Err(TarotError::RuleViolation(Action::Play(
+            *player_selected_card,
+        )))
     }
 }
 
 pub fn allowed_cards_to_play(trick: &Trick, player_cards: &[Card]) -> Vec<Card> {
     let mut allowed_cards = Vec::with_capacity(player_cards.len());
-    let played_suit = trick.played_suit();
+    // The Fool can always be played, whatever the played suit
+    let (fool, cards): (Vec<Card>, Vec<Card>) =
+        player_cards.iter().partition(|card| card.is_fool());
 
-    match played_suit {
-        None => allowed_cards.extend_from_slice(player_cards),
+    match trick.played_suit() {
+        None => allowed_cards.extend_from_slice(&cards),
         Some(played_suit) => {
-            let has_played_suit = player_cards
+            let has_played_suit = cards.iter().any(|card| card.suit.name == played_suit);
+            let trumps: Vec<Card> = cards
                 .iter()
-                .any(|card| card.suit.name == played_suit);
-            let has_trumps = player_cards.iter().any(|card| card.suit.is_trump());
-
-            if trick.played_suit() == Some(CardSuits::Trumps) {
-                if has_trumps {
-                    let best_played_trump_index = trick
-                        .get_best_played_card_index(Some(CardSuits::Trumps))
-                        .unwrap();
-                    let best_played_trump = trick.played_cards[best_played_trump_index];
-
-                    let superior_trumps: Vec<Card> = player_cards
-                        .iter()
-                        .filter(|card| {
-                            card.suit.is_trump()
-                                && card
-                                    .is_superior_than(&best_played_trump, Some(CardSuits::Trumps))
-                        })
-                        .cloned()
-                        .collect();
-
-                    if superior_trumps.is_empty() {
-                        // If no superior trumps are found, allow all trumps to be played
-                        for card in player_cards {
-                            if card.suit.is_trump() {
-                                allowed_cards.push(*card);
-                            }
-                        }
-                    } else {
-                        allowed_cards.extend(superior_trumps);
-                    }
-                } else {
-                    // If the player doesn't have trump cards, allow them to play any card
-                    allowed_cards.extend_from_slice(player_cards);
-                }
-            } else if has_played_suit {
+                .filter(|card| card.suit.is_trump())
+                .cloned()
+                .collect();
+
+            if has_played_suit && !played_suit.is_trump() {
                 // If the player has cards of the same suit as the first card played, only allow them to play those cards
-                for card in player_cards {
-                    if card.suit.name == played_suit {
-                        allowed_cards.push(*card);
-                    }
-                }
-            } else if has_trumps {
-                // If the player has trump cards, but not cards of the same suit as the first card played, only allow them to play trump cards
-                for card in player_cards {
-                    if card.suit.is_trump() {
-                        allowed_cards.push(*card);
-                    }
+                allowed_cards.extend(cards.iter().filter(|card| card.suit.name == played_suit));
+            } else if !trumps.is_empty() {
+                // The player must play a trump, and overtrump the best played trump when possible
+                let best_played_trump = trick
+                    .played_cards
+                    .iter()
+                    .filter(|card| card.suit.is_trump() && !card.is_fool())
+                    .map(|card| card.rank)
+                    .max()
+                    .unwrap_or(0);
+                let superior_trumps: Vec<Card> = trumps
+                    .iter()
+                    .filter(|card| card.rank > best_played_trump)
+                    .cloned()
+                    .collect();
+
+                if superior_trumps.is_empty() {
+                    allowed_cards.extend(trumps);
+                } else {
+                    allowed_cards.extend(superior_trumps);
                 }
             } else {
-                // If the player doesn't have cards of the same suit as the first card played, and doesn't have trump cards, allow them to play any card
-                allowed_cards.extend_from_slice(player_cards);
+                // If the player doesn't have cards of the played suit nor trump cards, allow them to play any card
+                allowed_cards.extend_from_slice(&cards);
             }
         }
     }
+    allowed_cards.extend(fool);
     allowed_cards
 }
@@ -1,7 +1,25 @@
 use rand::Rng;
-use std::str::FromStr;
+use std::io::{Error, ErrorKind};
+use std::sync::{mpsc::Receiver, Arc, Mutex};
 
-use super::card::Card;
+use super::{
+    card::Card,
+    error::TarotError,
+    locale::{fill, text, Messages},
+    render::{numbered, print_hand},
+};
+
+// Asked only when the player types '?', the advice may take a while to compute
+pub type HintSource<'a> = &'a dyn Fn() -> Option<String>;
+
+// The answers to the prompts of a player, when they do not come from the terminal
+pub type Lines = Arc<Mutex<Receiver<String>>>;
+
+enum Input {
+    Index(usize),
+    Hint,
+    Invalid,
+}
 
 pub fn random_int_in_range(min: usize, max: usize) -> usize {
     let mut rng = rand::thread_rng();
@@ -9,7 +27,7 @@ pub fn random_int_in_range(min: usize, max: usize) -> usize {
 }
 
 pub fn get_next_index<T>(vector: &[T], current_index: usize) -> usize {
-    if current_index < vector.len() - 1 {
+    if current_index + 1 < vector.len() {
         current_index + 1
     } else {
         0
@@ -43,47 +61,92 @@ fn display_enumeration<T: std::fmt::Display>(vector: &[T]) {
     println!();
 }
 
-fn prompt_selection() -> Result<usize, <usize as FromStr>::Err> {
+fn read_input(input: Option<&Lines>) -> Result<String, TarotError> {
+    let closed = || Error::new(ErrorKind::UnexpectedEof, "The input was closed").into();
+    if let Some(lines) = input {
+        let lines = lines.lock().map_err(|_| closed())?;
+        return lines.recv().map_err(|_| closed());
+    }
     let mut input = String::new();
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    input.trim().parse::<usize>()
+    if std::io::stdin().read_line(&mut input)? == 0 {
+        return Err(closed());
+    }
+    Ok(input)
+}
+
+fn prompt_selection(input: Option<&Lines>) -> Result<Input, TarotError> {
+    let input = read_input(input)?;
+    Ok(match input.trim() {
+        "?" => Input::Hint,
+        input => input.parse::<usize>().map_or(Input::Invalid, Input::Index),
+    })
 }
 
 pub fn select<T: std::fmt::Display + std::marker::Copy>(
     message: Option<&str>,
     from: Option<Vec<T>>,
-) -> Option<T> {
+) -> Result<T, TarotError> {
+    select_with_hint(message, from, None, None)
+}
+
+pub fn select_with_hint<T: std::fmt::Display + std::marker::Copy>(
+    message: Option<&str>,
+    from: Option<Vec<T>>,
+    hint: Option<HintSource>,
+    input: Option<&Lines>,
+) -> Result<T, TarotError> {
+    select_from(message, from, hint, input, &|from| {
+        display_enumeration(from)
+    })
+}
+
+// The hand is shown sorted by suit, with a number on each card allowed
+pub fn select_card(
+    message: &str,
+    hand: &[Card],
+    allowed: &[Card],
+    hint: Option<HintSource>,
+    input: Option<&Lines>,
+) -> Result<Card, TarotError> {
+    select_from(Some(message), Some(numbered(allowed)), hint, input, &|_| {
+        print_hand(hand, allowed)
+    })
+}
+
+fn select_from<T: std::marker::Copy>(
+    message: Option<&str>,
+    from: Option<Vec<T>>,
+    hint: Option<HintSource>,
+    input: Option<&Lines>,
+    show: &dyn Fn(&[T]),
+) -> Result<T, TarotError> {
     if let Some(message) = message {
         println!("\n{}", message);
     }
 
     match from {
-        Some(from) => {
-            println!("Select an option between 0 and {}", from.len() - 1);
-            display_enumeration(&from);
-            match prompt_selection() {
-                Ok(index) => {
-                    if index < from.len() {
-                        Some(from[index])
-                    } else {
-                        println!(
-                            "Invalid input. Please enter a number lower or equal than {}",
-                            from.len() - 1
-                        );
-                        select(message, Some(from))
-                    }
-                }
-                Err(_) => {
-                    println!("Invalid input. Please enter a number.");
-                    select(message, Some(from))
+        Some(from) if !from.is_empty() => loop {
+            let prompt = match hint {
+                Some(_) => Messages::SelectOptionOrHint,
+                None => Messages::SelectOption,
+            };
+            println!("{}", fill(prompt, &[&(from.len() - 1)]));
+            show(&from);
+            match prompt_selection(input)? {
+                Input::Index(index) if index < from.len() => return Ok(from[index]),
+                Input::Index(_) => {
+                    println!("{}", fill(Messages::InvalidIndex, &[&(from.len() - 1)]))
                 }
+                Input::Hint => match hint.and_then(|hint| hint()) {
+                    Some(advice) => println!("\n{}", fill(Messages::HintIs, &[&advice])),
+                    None => println!("\n{}", text(Messages::NoHint)),
+                },
+                Input::Invalid => println!("{}", text(Messages::InvalidNumber)),
             }
-        }
-        None => {
-            println!("\nNo options available");
-            None
+        },
+        _ => {
+            println!("\n{}", text(Messages::NoOptions));
+            Err(TarotError::NoAvailableChoice)
         }
     }
 }
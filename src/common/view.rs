@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    action::Action,
+    bid::Bids,
+    card::Card,
+    handle::{Handle, Handles},
+    phase::Phase,
+    taker::Taker,
+    trick::Trick,
+};
+
+// Everything a seat is allowed to know about the deal: its own hand and the public table
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatView {
+    pub seat: usize,
+    pub players: Vec<String>,
+    pub phase: Phase,
+    pub current: usize,
+    pub hand: Vec<Card>,
+    pub auction: Vec<(usize, Bids)>,
+    pub taker: Option<Taker>,
+    pub called_king: Option<Card>,
+    pub kitty: Option<Vec<Card>>,
+    pub ecart: Vec<Card>,
+    pub handles: Vec<Handle>,
+    pub trick: Trick,
+    pub tricks: Vec<Trick>,
+    pub legal_actions: Vec<Action>,
+}
+impl SeatView {
+    pub fn n_players(&self) -> usize {
+        self.players.len()
+    }
+    pub fn is_turn(&self) -> bool {
+        self.seat == self.current && !self.legal_actions.is_empty()
+    }
+    pub fn contract(&self) -> Bids {
+        self.taker.as_ref().map_or(Bids::Passe, |taker| taker.bid)
+    }
+    pub fn legal_cards(&self) -> Vec<Card> {
+        self.legal_actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::CallKing(card) | Action::Discard(card) | Action::Play(card) => Some(*card),
+                _ => None,
+            })
+            .collect()
+    }
+    pub fn legal_handles(&self) -> Vec<Handles> {
+        self.legal_actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Declare(handle) => *handle,
+                _ => None,
+            })
+            .collect()
+    }
+}
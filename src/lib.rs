@@ -1 +1,5 @@
+pub mod bots;
+pub mod cli;
 pub mod common;
+pub mod net;
+pub mod tui;
@@ -1,47 +1,126 @@
-// use std::env;
-// use std::process;
+use std::env;
+use std::process;
+use std::sync::{Arc, Mutex};
 
+use bots::analysis::analyze;
+use cli::options::{Commands, Options};
+use cli::renderer::LineRenderer;
+use common::arena::arena;
 use common::deal::{Deal, DealActions};
+use common::error::TarotError;
 use common::game::{Game, GameActions};
+use common::locale::{fill, set_language, text, Languages, Messages};
+use common::profile::{humans, record_deal, record_game, set_profiles, Profiles};
+use common::render::{set_color, terminal_supports_color};
+use common::simulation::simulate;
 use tarot_cli::*;
 
 fn main() {
-    println!("Let's play Tarot!");
+    if let Err(error) = run() {
+        eprintln!("\n{}", error);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), TarotError> {
+    let options = Options::parse(env::args().skip(1))?;
+    set_color(!options.no_color && terminal_supports_color());
+    set_language(options.language.unwrap_or_else(Languages::from_env));
+    set_profiles(Some(options.profiles()));
+    match options.command {
+        Commands::Play => play(&options),
+        Commands::Tui => tui::run(&options),
+        Commands::Serve => net::server::serve(&options),
+        Commands::Join => net::client::join(&options),
+        Commands::Lobby => net::lobby::lobby(&options),
+        Commands::Http => net::http::http(&options),
+        Commands::Stats => stats(&options),
+        Commands::Simulate => {
+            print!("{}", simulate(&options.simulation()?));
+            Ok(())
+        }
+        Commands::Arena => {
+            print!("{}", arena(&options.arena()?));
+            Ok(())
+        }
+    }
+}
 
-    let mut game = Game::default(); // TODO: use new() after prompt config from user
+fn play(options: &Options) -> Result<(), TarotError> {
+    println!("{}", text(Messages::Greeting));
+    let mut game = Game::new(options.n_players());
+    options.apply(&mut game.players)?;
+    game.events
+        .subscribe(Arc::new(Mutex::new(LineRenderer::default())));
     let mut deals = Vec::new();
 
     loop {
-        game.split_deck();
-        game.update_dealer();
-        game.reorder_players();
+        game.split_deck()?;
+        game.update_dealer()?;
+        game.reorder_players()?;
 
-        let mut deal = Deal::new(&mut game.players, &mut game.deck);
+        let mut deal = game.new_deal()?;
 
-        deal.take_bids();
+        deal.take_bids()?;
         match &deal.taker {
             None => {
-                println!("Nobody made a bid. Starting a new deal...");
+                println!("{}", text(Messages::NobodyBid));
+                game.collect_deck(&deal)?;
                 continue;
             }
             Some(taker) => {
-                println!(
-                    "The taker is {} with a bid of {:?}",
-                    taker.player.name, taker.bid
-                );
+                let name = &deal.players[taker.seat].name;
+                println!("{}", fill(Messages::TakerIs, &[name, &taker.bid]));
             }
         }
-        deal.call_king(); // TODO
-        deal.compose_kitty();
-        deal.play_tricks();
+        deal.call_king()?;
+        deal.compose_kitty()?;
+        deal.declare_handles()?;
+        deal.play_tricks()?;
 
-        deal.compute_score();
-        deal.show_score();
+        deal.compute_score()?;
+        record_deal(&deal);
+        if options.analysis {
+            show_analysis(&deal)?;
+        }
 
-        game.collect_deck(&deal.players);
+        game.collect_deck(&deal)?;
         deals.push(deal);
         break;
     }
+    record_game(&humans(&game.players));
+    println!("\n\n{}", text(Messages::Thanks));
+    Ok(())
+}
 
-    println!("\n\nThanks for playing !");
+fn stats(options: &Options) -> Result<(), TarotError> {
+    let path = options.profiles();
+    let profiles = Profiles::load(&path)?;
+    match &options.name {
+        _ if profiles.players.is_empty() => {
+            println!("{}", fill(Messages::NoProfiles, &[&path.display()]))
+        }
+        Some(name) => match profiles.players.get(name) {
+            Some(profile) => print!("{}\n{}", name, profile),
+            None => println!("{}", fill(Messages::UnknownProfile, &[name])),
+        },
+        None => print!("{}", profiles),
+    }
+    Ok(())
+}
+
+fn show_analysis(deal: &Deal) -> Result<(), TarotError> {
+    let humans: Vec<usize> = (0..deal.players.len())
+        .filter(|seat| deal.players[*seat].is_human)
+        .collect();
+    let mistakes = analyze(&deal.record, &humans)?;
+    println!("\n{}", text(Messages::AnalysisTitle));
+    if mistakes.is_empty() {
+        println!("{}", text(Messages::NoMistake));
+    }
+    for mistake in mistakes {
+        let name = &deal.players[mistake.seat].name;
+        println!("{}", fill(Messages::PlayerMistake, &[name, &mistake]));
+    }
+    Ok(())
 }
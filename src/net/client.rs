@@ -0,0 +1,179 @@
+use std::io::{BufRead, BufReader};
+use std::net::TcpStream;
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use super::protocol::{
+    post, receive, ClientMessage, Reveals, ServerMessage, TableInfo, TableStates,
+};
+use crate::cli::{options::Options, renderer::LineRenderer};
+use crate::common::{
+    error::TarotError,
+    event::Observer,
+    locale::{fill, text, Messages},
+    player::{Player, PlayerActions},
+    render::{color, paint_all, print_hand},
+};
+
+// The line interface of a local game, with the deal played on the server
+pub fn join(options: &Options) -> Result<(), TarotError> {
+    let address = options.address();
+    let stream = TcpStream::connect(&address)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let writer = Arc::new(Mutex::new(stream));
+    let name = options
+        .name
+        .clone()
+        .unwrap_or_else(|| String::from(text(Messages::DefaultName)));
+    // A client coming back with its token takes its seat again
+    let hello = match options.token.clone() {
+        Some(token) => ClientMessage::Resume { token },
+        None => ClientMessage::Hello { name: name.clone() },
+    };
+    post(&writer, &hello)?;
+
+    // The commands for the lobby start with a slash, the other lines answer the prompts
+    let (lines, input) = channel();
+    let commands = Arc::clone(&writer);
+    thread::spawn(move || {
+        for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+            if !line.starts_with('/') {
+                if lines.send(line).is_err() {
+                    return;
+                }
+                continue;
+            }
+            match parse_command(&line) {
+                Ok(message) => {
+                    if post(&commands, &message).is_err() {
+                        return;
+                    }
+                }
+                Err(reason) => println!("{}", reason),
+            }
+        }
+    });
+
+    let mut player = Player::new(name, 0);
+    player.is_human = true;
+    player.input = Some(Arc::new(Mutex::new(input)));
+    player.search = options.search;
+    let mut renderer = LineRenderer::default();
+    loop {
+        let Some(message) = receive(&mut reader)? else {
+            let reason = String::from(text(Messages::ClientClosed));
+            return Err(TarotError::Protocol(reason));
+        };
+        match message {
+            ServerMessage::Welcome { id, name, token } => {
+                player.id = id;
+                println!("{}", fill(Messages::ClientJoined, &[&address, &name]));
+                println!("{}", fill(Messages::ClientToken, &[&token, &token]));
+            }
+            ServerMessage::Event { event } => renderer.notify(&event),
+            ServerMessage::View { view } => {
+                println!("\n{}", text(Messages::YourCards));
+                print_hand(&view.hand, &[]);
+            }
+            ServerMessage::Prompt { view } => {
+                let action = player.act(&view)?;
+                post(&writer, &ClientMessage::Action { action })?;
+            }
+            ServerMessage::Rejected { reason } => println!("{}", reason),
+            ServerMessage::Goodbye { reason } => {
+                println!("\n{}", reason);
+                return Ok(());
+            }
+            ServerMessage::Tables { tables } if tables.is_empty() => {
+                println!("{}", text(Messages::NoTables))
+            }
+            ServerMessage::Tables { tables } => {
+                for table in &tables {
+                    println!("{}", describe(table));
+                }
+            }
+            ServerMessage::Seated { table, id } => {
+                player.id = id;
+                println!("{}", fill(Messages::ClientSeated, &[&table, &id]));
+            }
+            ServerMessage::Chat { name, text } => println!("{}: {}", name, text),
+            ServerMessage::Notice { text } => println!("{}", text),
+            ServerMessage::Hands {
+                players,
+                hands,
+                kitty,
+            } => {
+                for (name, hand) in players.iter().zip(hands.iter()) {
+                    println!("\n{}", fill(Messages::HandsOf, &[name]));
+                    print_hand(hand, &[]);
+                }
+                println!(
+                    "\n{}",
+                    fill(Messages::LogKitty, &[&paint_all(&kitty, color())])
+                );
+            }
+        }
+    }
+}
+
+// Reads a lobby command like `/create 4 2`, the help is given as the error
+pub fn parse_command(line: &str) -> Result<ClientMessage, String> {
+    let mut words = line.split_whitespace();
+    let command = words.next().unwrap_or_default();
+    let help = || String::from(text(Messages::LobbyHelp));
+    let number = |word: Option<&str>, default: Option<usize>| match word {
+        Some(word) => word.parse::<usize>().map_err(|_| help()),
+        None => default.ok_or_else(help),
+    };
+    let message = match command {
+        "/tables" => ClientMessage::List,
+        "/create" => ClientMessage::Create {
+            players: number(words.next(), None)?.min(u8::MAX as usize) as u8,
+            seats: number(words.next(), None)?,
+            deals: number(words.next(), Some(1))?,
+            reveal: match words.next() {
+                None => Reveals::Never,
+                Some("end") => Reveals::AtEnd,
+                Some(tricks) => Reveals::AfterTricks(tricks.parse().map_err(|_| help())?),
+            },
+        },
+        "/sit" => ClientMessage::Sit {
+            table: number(words.next(), None)?,
+        },
+        "/leave" => ClientMessage::Leave,
+        "/restart" => ClientMessage::Restart,
+        "/say" => ClientMessage::Say {
+            text: line[command.len()..].trim().to_string(),
+        },
+        "/watch" => ClientMessage::Watch {
+            table: number(words.next(), None)?,
+        },
+        "/help" => return Err(help()),
+        _ => return Err(fill(Messages::UnknownCommand, &[&command])),
+    };
+    Ok(message)
+}
+
+pub fn describe(table: &TableInfo) -> String {
+    let state = match table.state {
+        TableStates::Waiting => text(Messages::TableWaiting),
+        TableStates::Playing => text(Messages::TablePlaying),
+        TableStates::Over => text(Messages::TableOver),
+    };
+    fill(
+        Messages::TableLine,
+        &[
+            &table.id,
+            &table.players,
+            &table.seated.len(),
+            &table.seats,
+            &table.deals,
+            &table.spectators,
+            &state,
+            &table.seated.join(", "),
+        ],
+    )
+    .trim_end()
+    .to_string()
+}
@@ -0,0 +1,471 @@
+//! A local HTTP server exposing the deal engine as a JSON API, so a front end can
+//! use the rules of tarot-cli as its backend.
+//!
+//! A game is created for a number of players, the first `seats` players are driven
+//! through the API and the others are bots, which play as soon as it is their turn.
+//! Players are named by their id, which does not change when the dealer moves.
+//! Bodies and answers are JSON, with the cards, actions and events written like in
+//! the network protocol. A refused request is answered with an `error` field.
+//!
+//! The answer to the creation holds a secret token for every seat of the API. The
+//! view of a player and its actions need the token of its seat, sent as
+//! `Authorization: Bearer <token>`, so a front end gives each player only its own.
+//!
+//! ```text
+//! POST /games                        {"players":4,"seats":1,"deals":2}
+//!                                    {"game":{"id":1,...},"tokens":[{"player":1,"token":"6f1c..."}]}
+//! GET  /games/1                      the game, with its players and the deals played
+//! GET  /games/1/players/1            the view of player 1, with its legal actions on its turn
+//! POST /games/1/players/1/actions    {"Bid":"Garde"}
+//! GET  /games/1/history              {"events":[{"DealStarted":{...}},...]}
+//! GET  /games/1/scores               {"deals":[{"bid":"Garde",...}],"totals":[...]}
+//! ```
+//!
+//! An accepted action is answered with the view of the player once the bots played.
+//! A missing token gets `401 Unauthorized`, the token of another seat `403 Forbidden`,
+//! an action out of turn `409 Conflict` and an illegal one `422 Unprocessable Entity`.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::protocol::new_token;
+use crate::cli::options::Options;
+use crate::common::{
+    action::Action,
+    deal::{Deal, DealActions},
+    error::TarotError,
+    event::EventLog,
+    game::{Game, GameActions},
+    locale::{fill, Messages},
+    phase::Phase,
+    player::PlayerActions,
+    score::DealScore,
+    view::SeatView,
+};
+
+const MAX_BODY: usize = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewGame {
+    pub players: u8,
+    #[serde(default = "one")]
+    pub seats: usize,
+    #[serde(default = "one")]
+    pub deals: usize,
+}
+
+fn one() -> usize {
+    1
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameInfo {
+    pub id: usize,
+    pub players: Vec<String>,
+    pub seats: usize,
+    pub deals: usize,
+    pub played: usize,
+    // The player expected to act through the API, none while the game is over
+    pub waiting: Option<u8>,
+    pub over: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatToken {
+    pub player: u8,
+    pub token: String,
+}
+
+// The tokens are only given once, with the new game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Created {
+    pub game: GameInfo,
+    pub tokens: Vec<SeatToken>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Total {
+    pub id: u8,
+    pub name: String,
+    pub points: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scores {
+    pub deals: Vec<DealScore>,
+    pub totals: Vec<Total>,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+impl Response {
+    fn json<T: Serialize>(status: u16, body: &T) -> Self {
+        Response {
+            status,
+            body: serde_json::to_string(body).unwrap_or_default(),
+        }
+    }
+
+    fn error(status: u16, reason: &str) -> Self {
+        Response::json(status, &json!({ "error": reason }))
+    }
+}
+
+struct Session {
+    game: Game,
+    deal: Deal,
+    seats: usize,
+    // By player id, for the seats of the API
+    tokens: Vec<String>,
+    deals: usize,
+    played: usize,
+    history: Arc<Mutex<EventLog>>,
+    scores: Vec<DealScore>,
+    // By player id, from the first player
+    totals: Vec<f64>,
+}
+
+// Every game has its own lock, the bots of one game never hold the others
+#[derive(Default)]
+pub struct Api {
+    games: Mutex<BTreeMap<usize, Arc<Mutex<Session>>>>,
+}
+
+pub type SharedApi = Arc<Api>;
+
+pub fn http(options: &Options) -> Result<(), TarotError> {
+    let listener = TcpListener::bind(options.address())?;
+    println!("{}", fill(Messages::HttpOpen, &[&listener.local_addr()?]));
+    open(listener, SharedApi::default())
+}
+
+// Every connection carries one request, on its own thread
+pub fn open(listener: TcpListener, api: SharedApi) -> Result<(), TarotError> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let api = api.clone();
+        thread::spawn(move || {
+            if let Err(error) = answer(stream, &api) {
+                eprintln!("{}", error);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn answer(stream: TcpStream, api: &Api) -> Result<(), TarotError> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut words = line.split_whitespace();
+    let (method, path) = (
+        words.next().unwrap_or_default(),
+        words.next().unwrap_or("/"),
+    );
+    let mut length = 0;
+    let mut token = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            length = value.trim().parse().unwrap_or_default();
+        } else if name.trim().eq_ignore_ascii_case("authorization") {
+            token = value
+                .trim()
+                .strip_prefix("Bearer ")
+                .map(|t| t.trim().to_string());
+        }
+    }
+    let response = match length <= MAX_BODY {
+        true => {
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body)?;
+            let body = String::from_utf8_lossy(&body);
+            api.respond(method, path, token.as_deref(), &body)
+        }
+        false => Response::error(413, "The body is too large"),
+    };
+    write(stream, &response)
+}
+
+fn write(mut stream: TcpStream, response: &Response) -> Result<(), TarotError> {
+    let reason = match response.status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        _ => "Internal Server Error",
+    };
+    // A front end served from another origin can call the API
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type, Authorization\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+impl Api {
+    pub fn respond(&self, method: &str, path: &str, token: Option<&str>, body: &str) -> Response {
+        let path = path.split('?').next().unwrap_or_default();
+        let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+        if method == "OPTIONS" {
+            return Response {
+                status: 204,
+                body: String::new(),
+            };
+        }
+        let result = match (method, parts.as_slice()) {
+            ("POST", ["games"]) => self.create(body),
+            ("GET", ["games", id]) => self.with(id, |id, s| Ok(Response::json(200, &s.info(id)))),
+            ("GET", ["games", id, "players", player]) => {
+                self.with(id, |_, s| s.view(player, token))
+            }
+            ("POST", ["games", id, "players", player, "actions"]) => {
+                self.with(id, |_, s| s.act(player, token, body))
+            }
+            ("GET", ["games", id, "history"]) => self.with(id, |_, s| Ok(s.history())),
+            ("GET", ["games", id, "scores"]) => self.with(id, |_, s| Ok(s.scores())),
+            _ => Err(Response::error(404, "Unknown path")),
+        };
+        result.unwrap_or_else(|response| response)
+    }
+
+    // The bots play their first turns before the game is listed
+    fn create(&self, body: &str) -> Result<Response, Response> {
+        let new: NewGame =
+            serde_json::from_str(body).map_err(|error| Response::error(400, &error.to_string()))?;
+        if !(3..=5).contains(&new.players)
+            || !(1..=new.players as usize).contains(&new.seats)
+            || new.deals == 0
+        {
+            return Err(Response::error(
+                400,
+                "A game needs 3 to 5 players, 1 seat or more for the API and 1 deal or more",
+            ));
+        }
+        let session =
+            Session::new(new).map_err(|error| Response::error(500, &error.to_string()))?;
+        let tokens = session
+            .tokens
+            .iter()
+            .enumerate()
+            .map(|(index, token)| SeatToken {
+                player: index as u8 + 1,
+                token: token.clone(),
+            })
+            .collect();
+        let mut games = self.games.lock().map_err(|_| unavailable())?;
+        let id = games.keys().next_back().map_or(1, |last| last + 1);
+        let game = session.info(id);
+        games.insert(id, Arc::new(Mutex::new(session)));
+        Ok(Response::json(201, &Created { game, tokens }))
+    }
+
+    // Only the game asked for is locked while it answers
+    fn with(
+        &self,
+        id: &str,
+        answer: impl FnOnce(usize, &mut Session) -> Result<Response, Response>,
+    ) -> Result<Response, Response> {
+        let id = id.parse().unwrap_or_default();
+        let games = self.games.lock().map_err(|_| unavailable())?;
+        let session = games.get(&id).cloned();
+        drop(games);
+        let session = session.ok_or_else(|| Response::error(404, "No such game"))?;
+        let mut session = session.lock().map_err(|_| unavailable())?;
+        answer(id, &mut session)
+    }
+}
+
+fn unavailable() -> Response {
+    Response::error(500, "The game is unavailable")
+}
+
+impl Session {
+    fn new(new: NewGame) -> Result<Self, TarotError> {
+        let mut game = Game::new(new.players);
+        for player in game.players.iter_mut() {
+            player.is_human = false;
+        }
+        let history = Arc::new(Mutex::new(EventLog::default()));
+        game.events.subscribe(history.clone());
+        let deal = next_deal(&mut game)?;
+        let mut session = Session {
+            game,
+            deal,
+            seats: new.seats,
+            tokens: (0..new.seats).map(|_| new_token()).collect(),
+            deals: new.deals,
+            played: 0,
+            history,
+            scores: Vec::new(),
+            totals: vec![0.0; new.players as usize],
+        };
+        session.advance()?;
+        Ok(session)
+    }
+
+    fn over(&self) -> bool {
+        self.played >= self.deals
+    }
+
+    fn waiting(&self) -> Option<u8> {
+        let id = self.deal.current_player().id;
+        (!self.over() && id as usize <= self.seats).then_some(id)
+    }
+
+    // The bots play until a player of the API has to act, the deals follow each other
+    fn advance(&mut self) -> Result<(), TarotError> {
+        while !self.over() {
+            match self.deal.phase {
+                Phase::Scoring | Phase::Done => {
+                    if self.deal.phase == Phase::Scoring {
+                        self.deal.compute_score()?;
+                    }
+                    if let Some(score) = self.deal.score.clone() {
+                        for (seat, points) in score.scores.iter().enumerate() {
+                            let id = self.deal.players[seat].id as usize;
+                            self.totals[id - 1] += points;
+                        }
+                        self.scores.push(score);
+                    }
+                    if self.deal.taker.is_some() {
+                        self.played += 1;
+                    }
+                    self.game.collect_deck(&self.deal)?;
+                    if !self.over() {
+                        self.deal = next_deal(&mut self.game)?;
+                    }
+                }
+                _ if self.waiting().is_some() => return Ok(()),
+                _ => {
+                    // The bot only knows the view of the seat it plays
+                    let view = self.deal.view(self.deal.current);
+                    let action = self.deal.current_player().clone().act(&view)?;
+                    self.deal.apply(action)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn seat(&self, player: &str) -> Result<usize, Response> {
+        let id = player.parse::<u8>().unwrap_or_default();
+        self.deal
+            .players
+            .iter()
+            .position(|player| player.id == id)
+            .ok_or_else(|| Response::error(404, "No such player"))
+    }
+
+    // The seat of a player whose token was given, bots have none
+    fn authorize(&self, player: &str, token: Option<&str>) -> Result<usize, Response> {
+        let seat = self.seat(player)?;
+        let token = token.ok_or_else(|| Response::error(401, "The token of the seat is needed"))?;
+        let id = self.deal.players[seat].id as usize;
+        match self.tokens.get(id - 1) {
+            Some(expected) if expected == token => Ok(seat),
+            _ => Err(Response::error(
+                403,
+                "This token is not the one of the seat",
+            )),
+        }
+    }
+
+    fn info(&self, id: usize) -> GameInfo {
+        let mut players: Vec<_> = self.game.players.iter().collect();
+        players.sort_by_key(|player| player.id);
+        GameInfo {
+            id,
+            players: players.iter().map(|player| player.name.clone()).collect(),
+            seats: self.seats,
+            deals: self.deals,
+            played: self.played,
+            waiting: self.waiting(),
+            over: self.over(),
+        }
+    }
+
+    fn view(&self, player: &str, token: Option<&str>) -> Result<Response, Response> {
+        let seat = self.authorize(player, token)?;
+        Ok(Response::json(200, &self.deal.view(seat)))
+    }
+
+    fn act(&mut self, player: &str, token: Option<&str>, body: &str) -> Result<Response, Response> {
+        let seat = self.authorize(player, token)?;
+        let action: Action =
+            serde_json::from_str(body).map_err(|error| Response::error(400, &error.to_string()))?;
+        if self.waiting() != Some(self.deal.players[seat].id) {
+            return Err(Response::error(409, "It is not the turn of this player"));
+        }
+        let view: SeatView = self.deal.view(seat);
+        if !view.legal_actions.contains(&action) {
+            let reason = TarotError::RuleViolation(action).to_string();
+            return Err(Response::error(422, &reason));
+        }
+        let played = self.deal.apply(action).and_then(|_| self.advance());
+        played.map_err(|error| Response::error(500, &error.to_string()))?;
+        let seat = self.seat(player)?;
+        Ok(Response::json(200, &self.deal.view(seat)))
+    }
+
+    fn history(&self) -> Response {
+        let events = self
+            .history
+            .lock()
+            .map(|log| log.events.clone())
+            .unwrap_or_default();
+        Response::json(200, &json!({ "events": events }))
+    }
+
+    fn scores(&self) -> Response {
+        let mut players: Vec<_> = self.game.players.iter().collect();
+        players.sort_by_key(|player| player.id);
+        let totals = players
+            .iter()
+            .map(|player| Total {
+                id: player.id,
+                name: player.name.clone(),
+                points: self.totals[player.id as usize - 1],
+            })
+            .collect();
+        let scores = Scores {
+            deals: self.scores.clone(),
+            totals,
+        };
+        Response::json(200, &scores)
+    }
+}
+
+fn next_deal(game: &mut Game) -> Result<Deal, TarotError> {
+    game.split_deck()?;
+    game.update_dealer()?;
+    game.reorder_players()?;
+    game.new_deal()
+}
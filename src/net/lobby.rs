@@ -0,0 +1,541 @@
+use std::collections::BTreeMap;
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use super::protocol::{
+    forward, new_token, post, ClientMessage, Inbound, Link, Reveals, ServerMessage, TableInfo,
+    TableStates,
+};
+use super::server::{play_session, replace, Remote, SharedBroadcast};
+use super::spectator::{SharedSpectators, Spectators};
+use crate::cli::options::Options;
+use crate::common::{
+    error::TarotError,
+    game::Game,
+    locale::{fill, text, Messages},
+};
+
+// Everything the lobby hears about, from the connections and from the tables
+enum Signals {
+    Connected(usize, TcpStream),
+    Message(usize, Inbound),
+    SessionOver(usize),
+    // A spectator too far behind, or gone, no longer watches the table
+    Dropped(usize, usize),
+}
+
+struct Client {
+    name: Option<String>,
+    token: String,
+    link: Link,
+    table: Option<usize>,
+    watching: Option<usize>,
+    // Where the actions go while the client plays a session
+    seat: Option<Sender<Inbound>>,
+    seat_id: Option<u8>,
+    // Gone during a session, the seat is kept until the client resumes or the session ends
+    away: bool,
+}
+
+struct Table {
+    info: TableInfo,
+    // In the order of the seats
+    clients: Vec<usize>,
+    // The seated clients getting the events of the session
+    broadcast: SharedBroadcast,
+    spectators: SharedSpectators,
+}
+
+struct Lobby {
+    clients: BTreeMap<usize, Client>,
+    tables: BTreeMap<usize, Table>,
+    next_table: usize,
+    signals: Sender<Signals>,
+    grace: Duration,
+}
+
+pub fn lobby(options: &Options) -> Result<(), TarotError> {
+    let listener = TcpListener::bind(options.address())?;
+    println!("{}", fill(Messages::LobbyOpen, &[&listener.local_addr()?]));
+    open(listener, options.grace())
+}
+
+// Runs the lobby until the listener fails, every table plays its sessions on its own thread
+pub fn open(listener: TcpListener, grace: Duration) -> Result<(), TarotError> {
+    let (signals, inbox) = channel();
+    let mut lobby = Lobby {
+        clients: BTreeMap::new(),
+        tables: BTreeMap::new(),
+        next_table: 1,
+        signals: signals.clone(),
+        grace,
+    };
+    thread::spawn(move || welcome(listener, signals));
+    lobby.run(inbox);
+    Ok(())
+}
+
+fn welcome(listener: TcpListener, signals: Sender<Signals>) {
+    for (client, stream) in listener.incoming().enumerate() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let Ok(writer) = stream.try_clone() else {
+            continue;
+        };
+        if signals.send(Signals::Connected(client, writer)).is_err() {
+            return;
+        }
+        let signals = signals.clone();
+        thread::spawn(move || {
+            forward(BufReader::new(stream), |message| {
+                signals.send(Signals::Message(client, message)).is_ok()
+            })
+        });
+    }
+}
+
+impl Lobby {
+    fn run(&mut self, inbox: Receiver<Signals>) {
+        for signal in inbox {
+            match signal {
+                Signals::Connected(id, writer) => {
+                    let client = Client {
+                        name: None,
+                        token: new_token(),
+                        link: Arc::new(Mutex::new(writer)),
+                        table: None,
+                        watching: None,
+                        seat: None,
+                        seat_id: None,
+                        away: false,
+                    };
+                    self.clients.insert(id, client);
+                }
+                Signals::Message(id, Inbound::Message(message)) => {
+                    if let Err(reason) = self.handle(id, message) {
+                        self.send(id, &ServerMessage::Rejected { reason });
+                    }
+                }
+                Signals::Message(id, Inbound::Malformed(reason)) => {
+                    self.send(id, &ServerMessage::Rejected { reason })
+                }
+                Signals::Message(id, Inbound::Closed) => self.closed(id),
+                Signals::Message(_, Inbound::Resumed) => {}
+                Signals::SessionOver(table) => self.session_over(table),
+                Signals::Dropped(table, id) => self.dropped(table, id),
+            }
+        }
+    }
+
+    // A refused message is answered with the reason
+    fn handle(&mut self, id: usize, message: ClientMessage) -> Result<(), String> {
+        if let ClientMessage::Hello { name } = message {
+            let Some(client) = self.clients.get_mut(&id) else {
+                return Ok(());
+            };
+            client.name = Some(name.clone());
+            let token = client.token.clone();
+            self.send(id, &ServerMessage::Welcome { id: 0, name, token });
+            self.send(id, &self.tables());
+            return Ok(());
+        }
+        if let ClientMessage::Resume { token } = message {
+            return self.resume(id, &token);
+        }
+        let name = self
+            .clients
+            .get(&id)
+            .and_then(|client| client.name.clone())
+            .ok_or_else(|| String::from("Expected a hello"))?;
+        match message {
+            ClientMessage::Action { action } => {
+                let seat = self.clients[&id].seat.as_ref();
+                let seat = seat.ok_or_else(|| String::from(text(Messages::NotSeated)))?;
+                let _ = seat.send(Inbound::Message(ClientMessage::Action { action }));
+            }
+            ClientMessage::List => self.send(id, &self.tables()),
+            ClientMessage::Create {
+                players,
+                seats,
+                deals,
+                reveal,
+            } => self.create(players, seats, deals, reveal)?,
+            ClientMessage::Sit { table } => self.sit(id, &name, table)?,
+            ClientMessage::Leave => self.leave(id)?,
+            ClientMessage::Restart => self.restart(id)?,
+            ClientMessage::Watch { table } => self.watch(id, table)?,
+            ClientMessage::Say { text } => {
+                let table = self.seated_at(id)?;
+                self.tell(table, &ServerMessage::Chat { name, text });
+            }
+            ClientMessage::Hello { .. } | ClientMessage::Resume { .. } => {}
+        }
+        Ok(())
+    }
+
+    // Only a table of bots shows the hands to its spectators during the deal
+    fn create(
+        &mut self,
+        players: u8,
+        seats: usize,
+        deals: usize,
+        reveal: Reveals,
+    ) -> Result<(), String> {
+        if !(3..=5).contains(&players) || seats > players as usize || deals == 0 {
+            return Err(String::from(text(Messages::InvalidTable)));
+        }
+        if seats > 0 && matches!(reveal, Reveals::AfterTricks(_)) {
+            return Err(String::from(text(Messages::EarlyReveal)));
+        }
+        let id = self.next_table;
+        self.next_table += 1;
+        let signals = self.signals.clone();
+        let spectators = Spectators::new(reveal).on_drop(move |client| {
+            let _ = signals.send(Signals::Dropped(id, client));
+        });
+        let info = TableInfo {
+            id,
+            players,
+            seats,
+            deals,
+            reveal,
+            ..Default::default()
+        };
+        let table = Table {
+            info,
+            clients: Vec::new(),
+            broadcast: SharedBroadcast::default(),
+            spectators: Arc::new(Mutex::new(spectators)),
+        };
+        self.tables.insert(id, table);
+        let tables = self.tables();
+        let idle: Vec<usize> = self
+            .clients
+            .iter()
+            .filter(|(_, client)| client.table.is_none() && client.name.is_some())
+            .map(|(id, _)| *id)
+            .collect();
+        for id in idle {
+            self.send(id, &tables);
+        }
+        Ok(())
+    }
+
+    fn sit(&mut self, id: usize, name: &str, table: usize) -> Result<(), String> {
+        if self.clients[&id].table.is_some() || self.clients[&id].watching.is_some() {
+            return Err(String::from(text(Messages::AlreadySeated)));
+        }
+        let seated = self
+            .tables
+            .get_mut(&table)
+            .ok_or_else(|| fill(Messages::NoSuchTable, &[&table]))?;
+        if seated.info.state != TableStates::Waiting || seated.clients.len() >= seated.info.seats {
+            return Err(fill(Messages::TableFull, &[&table]));
+        }
+        seated.clients.push(id);
+        seated.info.seated.push(name.to_string());
+        let full = seated.clients.len() == seated.info.seats;
+        if let Some(client) = self.clients.get_mut(&id) {
+            client.table = Some(table);
+        }
+        let notice = fill(Messages::SatDown, &[&name, &table]);
+        self.tell(table, &ServerMessage::Notice { text: notice });
+        if full {
+            self.start(table);
+        }
+        Ok(())
+    }
+
+    // Spectators are not seated, they only follow the events of the table.
+    // A table of bots plays for its first spectator
+    fn watch(&mut self, id: usize, table: usize) -> Result<(), String> {
+        let client = &self.clients[&id];
+        if client.table.is_some() || client.watching.is_some() {
+            return Err(String::from(text(Messages::AlreadySeated)));
+        }
+        let link = client.link.clone();
+        let watched = self
+            .tables
+            .get_mut(&table)
+            .ok_or_else(|| fill(Messages::NoSuchTable, &[&table]))?;
+        if let Ok(mut spectators) = watched.spectators.lock() {
+            spectators.watch(id, link);
+        }
+        watched.info.spectators += 1;
+        let idle = watched.info.seats == 0 && watched.info.state == TableStates::Waiting;
+        if let Some(client) = self.clients.get_mut(&id) {
+            client.watching = Some(table);
+        }
+        let notice = fill(Messages::Watching, &[&table]);
+        self.send(id, &ServerMessage::Notice { text: notice });
+        if idle {
+            self.start(table);
+        }
+        Ok(())
+    }
+
+    // A client leaving a session in progress is replaced by a bot at once,
+    // and the table stops writing to it
+    fn leave(&mut self, id: usize) -> Result<(), String> {
+        if let Some(table) = self.clients.get_mut(&id).and_then(|c| c.watching.take()) {
+            if let Some(watched) = self.tables.get_mut(&table) {
+                if let Ok(mut spectators) = watched.spectators.lock() {
+                    spectators.leave(id);
+                }
+                watched.info.spectators -= 1;
+            }
+            return Ok(());
+        }
+        let table = self.seated_at(id)?;
+        let name = self.clients[&id].name.clone().unwrap_or_default();
+        let notice = fill(Messages::LeftTable, &[&name, &table]);
+        self.tell(table, &ServerMessage::Notice { text: notice });
+        let link = self.clients[&id].link.clone();
+        if let Some(client) = self.clients.get_mut(&id) {
+            client.table = None;
+            // With its sender dropped, the seat never waits for the client again
+            if let Some(seat) = client.seat.take() {
+                let _ = seat.send(Inbound::Closed);
+            }
+            client.seat_id = None;
+        }
+        if let Some(seated) = self.tables.get_mut(&table) {
+            if let Ok(mut broadcast) = seated.broadcast.lock() {
+                broadcast.leave(&link);
+            }
+            if let Some(seat) = seated.clients.iter().position(|client| *client == id) {
+                seated.clients.remove(seat);
+                seated.info.seated.remove(seat);
+            }
+            if seated.clients.is_empty() && seated.info.state != TableStates::Playing {
+                self.close(table);
+            }
+        }
+        Ok(())
+    }
+
+    fn dropped(&mut self, table: usize, id: usize) {
+        let watching = self.clients.get(&id).and_then(|client| client.watching);
+        if watching == Some(table) {
+            let _ = self.leave(id);
+        }
+    }
+
+    // A client gone during a session keeps its seat for a while, a bot plays for it
+    fn closed(&mut self, id: usize) {
+        let playing = self.clients.get(&id).and_then(|client| client.seat.clone());
+        match playing {
+            Some(seat) => {
+                let _ = seat.send(Inbound::Closed);
+                if let Some(client) = self.clients.get_mut(&id) {
+                    client.away = true;
+                }
+            }
+            None => {
+                let _ = self.leave(id);
+                self.clients.remove(&id);
+            }
+        }
+    }
+
+    // The new connection takes the place of the client gone with this token
+    fn resume(&mut self, id: usize, token: &str) -> Result<(), String> {
+        let old = self
+            .clients
+            .iter()
+            .find(|(_, client)| client.away && client.token == token)
+            .map(|(old, _)| *old)
+            .ok_or_else(|| String::from(text(Messages::UnknownToken)))?;
+        let Some(new) = self.clients.remove(&id) else {
+            return Ok(());
+        };
+        let Some(mut client) = self.clients.remove(&old) else {
+            return Ok(());
+        };
+        let stream = new.link.lock().ok().and_then(|link| link.try_clone().ok());
+        if let Some(stream) = stream {
+            replace(&client.link, stream);
+        }
+        client.away = false;
+        let name = client.name.clone().unwrap_or_default();
+        let (table, seat_id) = (client.table, client.seat_id);
+        let seat = client.seat.clone();
+        self.clients.insert(id, client);
+        let welcome = ServerMessage::Welcome {
+            id: 0,
+            name,
+            token: token.to_string(),
+        };
+        self.send(id, &welcome);
+        if let (Some(table), Some(seat_id)) = (table, seat_id) {
+            if let Some(seated) = self.tables.get_mut(&table) {
+                for client in seated.clients.iter_mut().filter(|client| **client == old) {
+                    *client = id;
+                }
+            }
+            self.send(id, &ServerMessage::Seated { table, id: seat_id });
+        }
+        if let Some(seat) = seat {
+            let _ = seat.send(Inbound::Resumed);
+        }
+        Ok(())
+    }
+
+    // The same players sit for a new session, with new scores
+    fn restart(&mut self, id: usize) -> Result<(), String> {
+        let table = self.seated_at(id)?;
+        let seated = self
+            .tables
+            .get_mut(&table)
+            .ok_or_else(|| fill(Messages::NoSuchTable, &[&table]))?;
+        if seated.info.state != TableStates::Over {
+            return Err(String::from(text(Messages::SessionRunning)));
+        }
+        seated.info.state = TableStates::Waiting;
+        if seated.clients.len() == seated.info.seats {
+            self.start(table);
+        } else {
+            self.tell(table, &self.tables());
+        }
+        Ok(())
+    }
+
+    fn start(&mut self, table: usize) {
+        let Some(seated) = self.tables.get_mut(&table) else {
+            return;
+        };
+        seated.info.state = TableStates::Playing;
+        let info = seated.info.clone();
+        let mut game = Game::new(info.players);
+        for player in game.players.iter_mut() {
+            player.is_human = false;
+        }
+        let mut remotes = Vec::new();
+        let mut links = Vec::new();
+        for (seat, id) in seated.clients.clone().into_iter().enumerate() {
+            let Some(client) = self.clients.get_mut(&id) else {
+                continue;
+            };
+            let player = &mut game.players[seat];
+            let name = client.name.clone().unwrap_or_default();
+            player.name = name.clone();
+            player.is_human = true;
+            let (sender, inbox) = channel();
+            client.seat = Some(sender);
+            client.seat_id = Some(player.id);
+            let (token, link) = (client.token.clone(), client.link.clone());
+            links.push(link.clone());
+            remotes.push(Remote::new(player.id, name, token, link, inbox));
+            let seated = ServerMessage::Seated {
+                table,
+                id: player.id,
+            };
+            let _ = post(&client.link, &seated);
+        }
+        if let Ok(mut broadcast) = seated.broadcast.lock() {
+            broadcast.clients = links;
+        }
+        let broadcast = seated.broadcast.clone();
+        let spectators = seated.spectators.clone();
+        let signals = self.signals.clone();
+        let grace = self.grace;
+        thread::spawn(move || {
+            let session = play_session(
+                &mut game,
+                &mut remotes,
+                info.deals,
+                &broadcast,
+                &spectators,
+                grace,
+            );
+            if let Err(error) = session {
+                eprintln!("{}", error);
+            }
+            let _ = signals.send(Signals::SessionOver(table));
+        });
+    }
+
+    fn session_over(&mut self, table: usize) {
+        let Some(seated) = self.tables.get_mut(&table) else {
+            return;
+        };
+        seated.info.state = TableStates::Over;
+        let away: Vec<usize> = seated
+            .clients
+            .iter()
+            .copied()
+            .filter(|id| self.clients.get(id).is_some_and(|client| client.away))
+            .collect();
+        for id in away {
+            if let Some(client) = self.clients.get_mut(&id) {
+                client.seat = None;
+            }
+            let _ = self.leave(id);
+            self.clients.remove(&id);
+        }
+        let Some(seated) = self.tables.get_mut(&table) else {
+            return;
+        };
+        if seated.clients.is_empty() {
+            self.close(table);
+            return;
+        }
+        for id in seated.clients.clone() {
+            if let Some(client) = self.clients.get_mut(&id) {
+                client.seat = None;
+                client.seat_id = None;
+            }
+        }
+        let notice = fill(Messages::SessionOver, &[&table]);
+        self.tell(table, &ServerMessage::Notice { text: notice });
+    }
+
+    // The spectators of a table closing are back in the lobby
+    fn close(&mut self, table: usize) {
+        self.tables.remove(&table);
+        for client in self.clients.values_mut() {
+            if client.watching == Some(table) {
+                client.watching = None;
+            }
+        }
+    }
+
+    fn seated_at(&self, id: usize) -> Result<usize, String> {
+        self.clients
+            .get(&id)
+            .and_then(|client| client.table)
+            .ok_or_else(|| String::from(text(Messages::NotSeated)))
+    }
+
+    fn tables(&self) -> ServerMessage {
+        let tables = self
+            .tables
+            .values()
+            .map(|table| table.info.clone())
+            .collect();
+        ServerMessage::Tables { tables }
+    }
+
+    // A client gone is removed when its connection ends
+    fn send(&mut self, id: usize, message: &ServerMessage) {
+        if let Some(client) = self.clients.get(&id) {
+            let _ = post(&client.link, message);
+        }
+    }
+
+    fn tell(&mut self, table: usize, message: &ServerMessage) {
+        let clients = self
+            .tables
+            .get(&table)
+            .map(|table| table.clients.clone())
+            .unwrap_or_default();
+        for id in clients {
+            self.send(id, message);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod client;
+pub mod http;
+pub mod lobby;
+pub mod protocol;
+pub mod server;
+pub mod spectator;
@@ -0,0 +1,242 @@
+//! The network protocol between a `serve` table and its `join` clients.
+//!
+//! Every message is a JSON object on its own line, with a `type` field. Cards are
+//! written with their `CardGetters::id` like in the notation, actions and events
+//! use the names of their Rust variants.
+//!
+//! A client opens the connection with its name, then the server tells it the
+//! player id it plays. The public events of the deal are sent to every client,
+//! while each seat only receives its own view: when the deal starts, and with
+//! every prompt for a decision. The client answers a prompt with one of the
+//! legal actions of the view, any other reply is rejected and the prompt sent again.
+//!
+//! ```text
+//! > {"type":"hello","name":"Alice"}
+//! < {"type":"welcome","id":2,"name":"Alice","token":"6f1c0a9e3b7d2485"}
+//! < {"type":"event","event":{"DealStarted":{"players":["Player 1","Alice","Player 3","Player 4"]}}}
+//! < {"type":"view","view":{"seat":1,"hand":["C3","H14","T21",...],...}}
+//! < {"type":"event","event":{"BidMade":{"seat":0,"bid":"Passe"}}}
+//! < {"type":"prompt","view":{"seat":1,"legal_actions":[{"Bid":"Petite"},...],...}}
+//! > {"type":"action","action":{"Bid":"Garde"}}
+//! < {"type":"prompt","view":{...,"legal_actions":[{"Play":"C3"},{"Play":"C7"}],...}}
+//! > {"type":"action","action":{"Play":"T21"}}
+//! < {"type":"rejected","reason":"Play T21 is not allowed"}
+//! < {"type":"prompt","view":{...}}
+//! < {"type":"goodbye","reason":"The game is over"}
+//! ```
+//!
+//! The welcome also gives the client a session token. A client losing its connection
+//! can come back with it instead of its name, and finds its seat with the history
+//! of the deal sent again. Until then, and if it is not back before the grace period
+//! ends, a bot of the level of its seat plays in its place.
+//!
+//! ```text
+//! > {"type":"resume","token":"6f1c0a9e3b7d2485"}
+//! < {"type":"welcome","id":2,"name":"Alice","token":"6f1c0a9e3b7d2485"}
+//! < {"type":"event","event":{"DealStarted":{"players":["Player 1","Alice","Player 3","Player 4"]}}}
+//! < ...
+//! < {"type":"view","view":{...}}
+//! ```
+//!
+//! A lobby hosts several tables on the same address. After the welcome, its
+//! clients list the tables, create one for a number of players with the seats
+//! left to remote players, sit at a table and leave it, restart the session of
+//! a table once it is over, and chat with the players of their table:
+//!
+//! ```text
+//! > {"type":"create","players":4,"seats":2,"deals":3}
+//! < {"type":"tables","tables":[{"id":1,"players":4,"seats":2,"deals":3,"seated":[],"spectators":0,"reveal":"Never","state":"Waiting"}]}
+//! > {"type":"sit","table":1}
+//! < {"type":"seated","table":1,"id":1}
+//! > {"type":"say","text":"Bon appétit"}
+//! < {"type":"chat","name":"Alice","text":"Bon appétit"}
+//! ```
+//!
+//! A client can also watch a table instead of sitting at it. Spectators get the
+//! public events of the table, and the hands as dealt once the reveal of the table
+//! allows it: never, after a number of tricks, or at the end of the deal. The reveal
+//! is chosen when the table is created, and only a table of bots, which starts with
+//! its first spectator, shows the hands before the end of the deal: a player could
+//! watch their own table from another connection. Spectators cannot act nor chat
+//! with the seats.
+//!
+//! ```text
+//! > {"type":"create","players":4,"seats":0,"deals":1,"reveal":{"AfterTricks":3}}
+//! > {"type":"watch","table":1}
+//! < {"type":"event","event":{"CardPlayed":{"seat":2,"card":"S7"}}}
+//! < {"type":"hands","players":["Alice","Bob",...],"hands":[["C3",...],...],"kitty":["D4",...]}
+//! ```
+
+use std::io::{BufRead, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+
+use rand::Rng;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::common::{action::Action, card::Card, error::TarotError, event::Event, view::SeatView};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Hello {
+        name: String,
+    },
+    Action {
+        action: Action,
+    },
+    List,
+    Create {
+        players: u8,
+        seats: usize,
+        deals: usize,
+        #[serde(default)]
+        reveal: Reveals,
+    },
+    Sit {
+        table: usize,
+    },
+    Leave,
+    Restart,
+    Say {
+        text: String,
+    },
+    Watch {
+        table: usize,
+    },
+    Resume {
+        token: String,
+    },
+}
+
+// What a server hears from a client connection
+#[derive(Debug)]
+pub enum Inbound {
+    Message(ClientMessage),
+    // The reason the line could not be read
+    Malformed(String),
+    Closed,
+    // The client came back on a new connection
+    Resumed,
+}
+
+// The connection of a client, replaced when it comes back
+pub type Link = Arc<Mutex<TcpStream>>;
+
+// When a spectator sees the hands of the deal
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Reveals {
+    #[default]
+    Never,
+    AfterTricks(usize),
+    AtEnd,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TableStates {
+    #[default]
+    Waiting,
+    Playing,
+    Over,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableInfo {
+    pub id: usize,
+    pub players: u8,
+    pub seats: usize,
+    pub deals: usize,
+    pub seated: Vec<String>,
+    pub spectators: usize,
+    pub reveal: Reveals,
+    pub state: TableStates,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Welcome {
+        id: u8,
+        name: String,
+        token: String,
+    },
+    Event {
+        event: Event,
+    },
+    View {
+        view: SeatView,
+    },
+    Prompt {
+        view: SeatView,
+    },
+    Rejected {
+        reason: String,
+    },
+    Goodbye {
+        reason: String,
+    },
+    Tables {
+        tables: Vec<TableInfo>,
+    },
+    Seated {
+        table: usize,
+        id: u8,
+    },
+    Chat {
+        name: String,
+        text: String,
+    },
+    Notice {
+        text: String,
+    },
+    Hands {
+        players: Vec<String>,
+        hands: Vec<Vec<Card>>,
+        kitty: Vec<Card>,
+    },
+}
+
+pub fn send<W: Write, T: Serialize>(out: &mut W, message: &T) -> Result<(), TarotError> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    out.write_all(line.as_bytes())?;
+    out.flush()?;
+    Ok(())
+}
+
+// None once the other side closed the connection
+pub fn receive<R: BufRead, T: DeserializeOwned>(input: &mut R) -> Result<Option<T>, TarotError> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_str(line.trim())?))
+}
+
+// Writes to the current connection of a client
+pub fn post<T: Serialize>(link: &Mutex<TcpStream>, message: &T) -> Result<(), TarotError> {
+    let mut stream = link
+        .lock()
+        .map_err(|_| TarotError::Protocol(String::from("The connection is unavailable")))?;
+    send(&mut *stream, message)
+}
+
+pub fn new_token() -> String {
+    format!("{:016x}", rand::thread_rng().gen::<u64>())
+}
+
+// Reads the messages of a client until it leaves or they are no longer delivered
+pub fn forward<R: BufRead>(mut input: R, mut deliver: impl FnMut(Inbound) -> bool) {
+    loop {
+        let message = match receive(&mut input) {
+            Ok(Some(message)) => Inbound::Message(message),
+            Err(TarotError::Protocol(reason)) => Inbound::Malformed(reason),
+            _ => break,
+        };
+        if !deliver(message) {
+            return;
+        }
+    }
+    deliver(Inbound::Closed);
+}
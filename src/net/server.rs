@@ -0,0 +1,449 @@
+use std::io::{BufReader, Error, ErrorKind};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::protocol::{
+    forward, new_token, post, receive, send, ClientMessage, Inbound, Link, ServerMessage,
+};
+use super::spectator::SharedSpectators;
+use crate::cli::{options::Options, renderer::LineRenderer};
+use crate::common::{
+    action::Action,
+    deal::{Deal, DealActions},
+    error::TarotError,
+    event::{Event, EventLog, Observer},
+    game::{Game, GameActions},
+    locale::{fill, text, Messages},
+    phase::Phase,
+    player::PlayerActions,
+    profile::{record_deal, record_game},
+    view::SeatView,
+};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+pub const DEFAULT_GRACE: Duration = Duration::from_secs(30);
+
+// A player sitting at the table from another terminal, its messages read from the inbox
+pub struct Remote {
+    pub id: u8,
+    pub name: String,
+    pub token: String,
+    pub connected: bool,
+    // Since when the client is gone, a bot plays for it once the grace period is over
+    away: Option<Instant>,
+    inbox: Receiver<Inbound>,
+    link: Link,
+}
+
+// What a new connection needs to take the seat of a remote back
+#[derive(Clone)]
+pub struct Seat {
+    pub id: u8,
+    pub name: String,
+    pub token: String,
+    pub link: Link,
+    pub inbox: Sender<Inbound>,
+}
+impl Seat {
+    // The messages of the new connection go to the inbox of the remote
+    pub fn resume(&self, reader: BufReader<TcpStream>) -> Result<(), TarotError> {
+        let mut stream = reader.get_ref().try_clone()?;
+        let welcome = ServerMessage::Welcome {
+            id: self.id,
+            name: self.name.clone(),
+            token: self.token.clone(),
+        };
+        send(&mut stream, &welcome)?;
+        replace(&self.link, stream);
+        let _ = self.inbox.send(Inbound::Resumed);
+        self.listen(reader);
+        Ok(())
+    }
+
+    // A connection replaced since does not close the seat anymore
+    fn listen(&self, reader: BufReader<TcpStream>) {
+        let (link, inbox) = (self.link.clone(), self.inbox.clone());
+        thread::spawn(move || {
+            let peer = reader.get_ref().peer_addr().ok();
+            forward(reader, |message| match message {
+                Inbound::Closed if current(&link) != peer => false,
+                message => inbox.send(message).is_ok(),
+            })
+        });
+    }
+}
+
+// The new connection of a client, the old one is closed
+pub fn replace(link: &Link, stream: TcpStream) {
+    if let Ok(mut current) = link.lock() {
+        let old = std::mem::replace(&mut *current, stream);
+        let _ = old.shutdown(Shutdown::Both);
+    }
+}
+
+fn current(link: &Link) -> Option<SocketAddr> {
+    link.lock().ok().and_then(|stream| stream.peer_addr().ok())
+}
+
+impl Remote {
+    pub fn new(id: u8, name: String, token: String, link: Link, inbox: Receiver<Inbound>) -> Self {
+        Remote {
+            id,
+            name,
+            token,
+            connected: true,
+            away: None,
+            inbox,
+            link,
+        }
+    }
+
+    pub fn accept(stream: TcpStream, id: u8) -> Result<(Self, Seat), TarotError> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let name = match receive(&mut reader)? {
+            Some(ClientMessage::Hello { name }) => name,
+            _ => return Err(TarotError::Protocol(String::from("Expected a hello"))),
+        };
+        let (sender, inbox) = channel();
+        let seat = Seat {
+            id,
+            name,
+            token: new_token(),
+            link: Arc::new(Mutex::new(stream)),
+            inbox: sender,
+        };
+        seat.listen(reader);
+        let mut remote = Remote::new(
+            id,
+            seat.name.clone(),
+            seat.token.clone(),
+            seat.link.clone(),
+            inbox,
+        );
+        let welcome = ServerMessage::Welcome {
+            id,
+            name: remote.name.clone(),
+            token: remote.token.clone(),
+        };
+        remote.send(&welcome)?;
+        Ok((remote, seat))
+    }
+
+    pub fn send(&mut self, message: &ServerMessage) -> Result<(), TarotError> {
+        post(&self.link, message)
+    }
+
+    pub fn disconnect(&mut self) {
+        if self.connected {
+            self.connected = false;
+            self.away = Some(Instant::now());
+            println!("{}", fill(Messages::ServerLeft, &[&self.name]));
+        }
+    }
+
+    // The client back at the table gets the events of the deal so far and its view
+    fn resume(&mut self, history: &Mutex<EventLog>, view: Option<SeatView>) {
+        self.connected = true;
+        self.away = None;
+        println!("{}", fill(Messages::ServerBack, &[&self.name]));
+        let events = history
+            .lock()
+            .map(|log| log.events.clone())
+            .unwrap_or_default();
+        for event in events {
+            let _ = self.send(&ServerMessage::Event { event });
+        }
+        if let Some(view) = view {
+            let _ = self.send(&ServerMessage::View { view });
+        }
+    }
+
+    fn view(&self, deal: &Deal) -> Option<SeatView> {
+        let seat = deal.players.iter().position(|p| p.id == self.id)?;
+        Some(deal.view(seat))
+    }
+
+    // Reads what the client sent while it was not its turn
+    pub fn poll(&mut self, deal: &Deal, history: &Mutex<EventLog>) {
+        while let Ok(message) = self.inbox.try_recv() {
+            let reason = match message {
+                Inbound::Closed => {
+                    self.disconnect();
+                    continue;
+                }
+                Inbound::Resumed => {
+                    self.resume(history, self.view(deal));
+                    continue;
+                }
+                Inbound::Message(ClientMessage::Action { .. }) => {
+                    String::from(text(Messages::NotYourTurn))
+                }
+                Inbound::Message(_) => String::from(text(Messages::NotInLobby)),
+                Inbound::Malformed(reason) => reason,
+            };
+            let _ = self.send(&ServerMessage::Rejected { reason });
+        }
+    }
+
+    // True once the client is at the table, waiting for it until the grace period ends
+    pub fn wait(&mut self, grace: Duration, deal: &Deal, history: &Mutex<EventLog>) -> bool {
+        while !self.connected {
+            let left = grace.saturating_sub(self.away.map_or(grace, |away| away.elapsed()));
+            match self.inbox.recv_timeout(left) {
+                Ok(Inbound::Resumed) => self.resume(history, self.view(deal)),
+                Ok(_) => {}
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => {
+                    return false
+                }
+            }
+        }
+        true
+    }
+
+    // Asks until the client answers with a legal action, other messages are only refused
+    pub fn ask(
+        &mut self,
+        view: &SeatView,
+        history: &Mutex<EventLog>,
+    ) -> Result<Action, TarotError> {
+        let prompt = ServerMessage::Prompt { view: view.clone() };
+        self.send(&prompt)?;
+        loop {
+            let (reason, again) = match self.inbox.recv() {
+                Ok(Inbound::Message(ClientMessage::Action { action })) => {
+                    if view.legal_actions.contains(&action) {
+                        return Ok(action);
+                    }
+                    (TarotError::RuleViolation(action).to_string(), true)
+                }
+                Ok(Inbound::Message(ClientMessage::Hello { .. })) => {
+                    (String::from("Already at the table"), false)
+                }
+                Ok(Inbound::Message(_)) => (String::from(text(Messages::NotInLobby)), false),
+                Ok(Inbound::Malformed(reason)) => (reason, true),
+                Ok(Inbound::Resumed) => {
+                    self.resume(history, Some(view.clone()));
+                    self.send(&prompt)?;
+                    continue;
+                }
+                Ok(Inbound::Closed) | Err(_) => {
+                    return Err(Error::from(ErrorKind::UnexpectedEof).into())
+                }
+            };
+            self.send(&ServerMessage::Rejected { reason })?;
+            if again {
+                self.send(&prompt)?;
+            }
+        }
+    }
+}
+
+// Sends every public event of the deal to the clients
+#[derive(Default)]
+pub struct Broadcast {
+    pub clients: Vec<Link>,
+}
+impl Broadcast {
+    // A client leaving the table gets nothing more from it
+    pub fn leave(&mut self, link: &Link) {
+        self.clients.retain(|client| !Arc::ptr_eq(client, link));
+    }
+}
+impl Observer for Broadcast {
+    fn notify(&mut self, event: &Event) {
+        let message = ServerMessage::Event {
+            event: event.clone(),
+        };
+        for client in self.clients.iter() {
+            // A client gone is noticed when its connection closes
+            let _ = post(client, &message);
+        }
+    }
+}
+
+pub type SharedBroadcast = Arc<Mutex<Broadcast>>;
+
+pub fn serve(options: &Options) -> Result<(), TarotError> {
+    let listener = TcpListener::bind(options.address())?;
+    let mut game = Game::new(options.n_players());
+    options.apply(&mut game.players)?;
+    game.events
+        .subscribe(Arc::new(Mutex::new(LineRenderer::default())));
+    let remote = options.remote()?;
+    println!(
+        "{}",
+        fill(Messages::ServerWaiting, &[&remote, &listener.local_addr()?])
+    );
+    host(
+        &listener,
+        game,
+        remote,
+        options.deals.unwrap_or(1),
+        options.grace(),
+    )
+}
+
+// Seats the first clients to join, then plays the deals with bots in the empty seats
+pub fn host(
+    listener: &TcpListener,
+    mut game: Game,
+    remote: usize,
+    deals: usize,
+    grace: Duration,
+) -> Result<(), TarotError> {
+    for player in game.players.iter_mut() {
+        player.is_human = false;
+    }
+    let mut remotes = Vec::new();
+    let mut seats = Vec::new();
+    while remotes.len() < remote {
+        let (stream, _) = listener.accept()?;
+        let player = &mut game.players[remotes.len()];
+        match Remote::accept(stream, player.id) {
+            Ok((client, seat)) => {
+                player.name = client.name.clone();
+                player.is_human = true;
+                println!("{}", fill(Messages::ServerJoined, &[&client.name]));
+                remotes.push(client);
+                seats.push(seat);
+            }
+            Err(error) => eprintln!("{}", error),
+        }
+    }
+    let listener = listener.try_clone()?;
+    thread::spawn(move || rejoin(listener, seats));
+
+    let clients = remotes.iter().map(|remote| remote.link.clone()).collect();
+    play_session(
+        &mut game,
+        &mut remotes,
+        deals,
+        &Arc::new(Mutex::new(Broadcast { clients })),
+        &SharedSpectators::default(),
+        grace,
+    )?;
+    let goodbye = ServerMessage::Goodbye {
+        reason: String::from(text(Messages::ServerGameOver)),
+    };
+    for remote in remotes.iter_mut().filter(|remote| remote.connected) {
+        let _ = remote.send(&goodbye);
+    }
+    Ok(())
+}
+
+// Once the table is full, only the clients coming back with their token are let in
+fn rejoin(listener: TcpListener, seats: Vec<Seat>) {
+    for stream in listener.incoming().map_while(Result::ok) {
+        let seats = seats.clone();
+        thread::spawn(move || {
+            let mut stream = stream;
+            let Ok(mut reader) = stream.try_clone().map(BufReader::new) else {
+                return;
+            };
+            let token = match receive(&mut reader) {
+                Ok(Some(ClientMessage::Resume { token })) => token,
+                _ => {
+                    let reason = String::from(text(Messages::TableTaken));
+                    let _ = send(&mut stream, &ServerMessage::Goodbye { reason });
+                    return;
+                }
+            };
+            match seats.iter().find(|seat| seat.token == token) {
+                Some(seat) => {
+                    let _ = seat.resume(reader);
+                }
+                None => {
+                    let reason = String::from(text(Messages::UnknownToken));
+                    let _ = send(&mut stream, &ServerMessage::Goodbye { reason });
+                }
+            }
+        });
+    }
+}
+
+// Plays the deals of a table whose remote players are seated, a passed deal is dealt again
+pub fn play_session(
+    game: &mut Game,
+    remotes: &mut [Remote],
+    deals: usize,
+    broadcast: &SharedBroadcast,
+    spectators: &SharedSpectators,
+    grace: Duration,
+) -> Result<(), TarotError> {
+    game.events.subscribe(broadcast.clone());
+    game.events.subscribe(spectators.clone());
+    let history = Arc::new(Mutex::new(EventLog::default()));
+    game.events.subscribe(history.clone());
+
+    let mut played = 0;
+    while played < deals {
+        game.split_deck()?;
+        game.update_dealer()?;
+        game.reorder_players()?;
+        if let Ok(mut history) = history.lock() {
+            history.events.clear();
+        }
+        let mut deal = game.new_deal()?;
+        if let Ok(mut spectators) = spectators.lock() {
+            spectators.deal(&deal.record);
+        }
+        play_deal(&mut deal, remotes, &history, grace)?;
+        if deal.taker.is_some() {
+            played += 1;
+            record_deal(&deal);
+        }
+        game.collect_deck(&deal)?;
+        for player in game.players.iter_mut() {
+            player.is_human = remotes.iter().any(|r| r.id == player.id && r.connected);
+        }
+    }
+    let names: Vec<String> = remotes.iter().map(|remote| remote.name.clone()).collect();
+    record_game(&names);
+    Ok(())
+}
+
+fn play_deal(
+    deal: &mut Deal,
+    remotes: &mut [Remote],
+    history: &Mutex<EventLog>,
+    grace: Duration,
+) -> Result<(), TarotError> {
+    for remote in remotes.iter_mut().filter(|remote| remote.connected) {
+        if let Some(view) = remote.view(deal) {
+            if remote.send(&ServerMessage::View { view }).is_err() {
+                remote.disconnect();
+            }
+        }
+    }
+    while !matches!(deal.phase, Phase::Scoring | Phase::Done) {
+        for remote in remotes.iter_mut() {
+            remote.poll(deal, history);
+        }
+        let view = deal.view(deal.current);
+        let id = deal.current_player().id;
+        let remote = remotes.iter_mut().find(|r| r.id == id);
+        let action = match remote.and_then(|r| r.wait(grace, deal, history).then_some(r)) {
+            Some(remote) => match remote.ask(&view, history) {
+                Ok(action) => action,
+                Err(_) => {
+                    remote.disconnect();
+                    continue;
+                }
+            },
+            // The bot only knows the view of the seat it plays
+            None => {
+                let mut bot = deal.current_player().clone();
+                bot.is_human = false;
+                bot.act(&view)?
+            }
+        };
+        deal.apply(action)?;
+    }
+    if deal.phase == Phase::Scoring {
+        deal.compute_score()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use super::protocol::{send, Link, Reveals, ServerMessage};
+use crate::common::{
+    card::Card,
+    event::{Event, Observer},
+    notation::DealRecord,
+};
+
+// A spectator further behind than this is dropped rather than waited for
+const BACKLOG: usize = 256;
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Watcher {
+    client: usize,
+    revealed: bool,
+    outbox: SyncSender<ServerMessage>,
+}
+
+// The watchers of a table: they follow the public events like the seats, and get
+// the hands only when the reveal of the table allows it, so nothing they see comes back to it
+#[derive(Default)]
+pub struct Spectators {
+    reveal: Reveals,
+    watchers: Vec<Watcher>,
+    // Told about the watchers dropped by the table
+    dropped: Option<Box<dyn Fn(usize) + Send>>,
+    players: Vec<String>,
+    hands: Vec<Vec<Card>>,
+    kitty: Vec<Card>,
+    tricks: usize,
+    over: bool,
+}
+
+pub type SharedSpectators = Arc<Mutex<Spectators>>;
+
+impl Spectators {
+    pub fn new(reveal: Reveals) -> Self {
+        Spectators {
+            reveal,
+            ..Default::default()
+        }
+    }
+
+    pub fn on_drop(self, dropped: impl Fn(usize) + Send + 'static) -> Self {
+        Spectators {
+            dropped: Some(Box::new(dropped)),
+            ..self
+        }
+    }
+
+    // Every spectator has its own writer, so the table never waits for a slow connection.
+    // The write timeout is an option of the socket, so it only lasts while the writer runs
+    pub fn watch(&mut self, client: usize, link: Link) {
+        self.leave(client);
+        let Some(mut stream) = link.lock().ok().and_then(|stream| stream.try_clone().ok()) else {
+            if let Some(report) = &self.dropped {
+                report(client);
+            }
+            return;
+        };
+        let (outbox, inbox) = sync_channel(BACKLOG);
+        thread::spawn(move || {
+            let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+            for message in inbox {
+                // The link is only held to keep the messages whole
+                let Ok(_link) = link.lock() else {
+                    break;
+                };
+                if send(&mut stream, &message).is_err() {
+                    break;
+                }
+            }
+            let _ = stream.set_write_timeout(None);
+        });
+        self.watchers.push(Watcher {
+            client,
+            revealed: false,
+            outbox,
+        });
+        self.reveal();
+    }
+
+    pub fn leave(&mut self, client: usize) {
+        self.watchers.retain(|watcher| watcher.client != client);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watchers.is_empty()
+    }
+
+    // The hands as dealt, kept until the reveal of the table
+    pub fn deal(&mut self, record: &DealRecord) {
+        self.players = record.players.clone();
+        self.hands = record.hands.clone();
+        self.kitty = record.kitty.clone();
+        self.tricks = 0;
+        self.over = false;
+        for watcher in self.watchers.iter_mut() {
+            watcher.revealed = false;
+        }
+        self.reveal();
+    }
+
+    fn reveal(&mut self) {
+        let revealed = match self.reveal {
+            Reveals::Never => false,
+            Reveals::AfterTricks(tricks) => self.tricks >= tricks || self.over,
+            Reveals::AtEnd => self.over,
+        };
+        if self.hands.is_empty() || !revealed {
+            return;
+        }
+        let hands = ServerMessage::Hands {
+            players: self.players.clone(),
+            hands: self.hands.clone(),
+            kitty: self.kitty.clone(),
+        };
+        let mut fresh = Vec::new();
+        for watcher in self.watchers.iter_mut().filter(|watcher| !watcher.revealed) {
+            watcher.revealed = true;
+            fresh.push(watcher.client);
+        }
+        self.post(&hands, |watcher| fresh.contains(&watcher.client));
+    }
+
+    // A watcher whose writer is gone or too far behind stops watching
+    fn post(&mut self, message: &ServerMessage, to: impl Fn(&Watcher) -> bool) {
+        let mut dropped = Vec::new();
+        self.watchers.retain(|watcher| {
+            let kept = !to(watcher) || watcher.outbox.try_send(message.clone()).is_ok();
+            if !kept {
+                dropped.push(watcher.client);
+            }
+            kept
+        });
+        if let Some(report) = &self.dropped {
+            dropped.into_iter().for_each(report);
+        }
+    }
+}
+impl Observer for Spectators {
+    fn notify(&mut self, event: &Event) {
+        let message = ServerMessage::Event {
+            event: event.clone(),
+        };
+        self.post(&message, |_| true);
+        match event {
+            Event::TrickWon { .. } => self.tricks += 1,
+            Event::DealScored { .. } => self.over = true,
+            _ => return,
+        }
+        self.reveal();
+    }
+}
@@ -0,0 +1,100 @@
+pub use crate::common::render::{label, labels, sorted};
+use crate::common::{
+    action::Action,
+    event::Event,
+    locale::{fill, text, Messages},
+    phase::Phase,
+    view::SeatView,
+};
+
+const LOG_SIZE: usize = 6;
+
+// Everything the full-screen front end keeps between two frames
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct App {
+    pub seat: usize,
+    pub cursor: usize,
+    pub deals: usize,
+    pub players: Vec<String>,
+    // Running totals by player name, the seats change with the dealer
+    pub scores: Vec<(String, f64)>,
+    pub log: Vec<String>,
+    pub message: Option<String>,
+}
+impl App {
+    pub fn record(&mut self, event: &Event) {
+        let name = |seat: &usize| self.players.get(*seat).cloned().unwrap_or_default();
+        let line = match event {
+            Event::DealerChosen { name } => fill(Messages::LogDeals, &[name]),
+            Event::DealStarted { players } => {
+                self.players = players.clone();
+                self.deals += 1;
+                for player in players {
+                    if !self.scores.iter().any(|(name, _)| name == player) {
+                        self.scores.push((player.clone(), 0.0));
+                    }
+                }
+                fill(Messages::LogDealStarts, &[&self.deals])
+            }
+            Event::BidMade { seat, bid } => fill(Messages::LogBid, &[&name(seat), bid]),
+            Event::KingCalled { seat, king } => {
+                fill(Messages::LogKingCalled, &[&name(seat), &label(king)])
+            }
+            Event::KittyRevealed { cards } => fill(Messages::LogKitty, &[&labels(cards)]),
+            Event::HandleDeclared { handle } => fill(
+                Messages::LogHandle,
+                &[&name(&handle.seat), &handle.kind.label()],
+            ),
+            Event::CardPlayed { seat, card } => {
+                fill(Messages::LogCardPlayed, &[&name(seat), &label(card)])
+            }
+            Event::TrickWon { seat, .. } => fill(Messages::LogTrickWon, &[&name(seat)]),
+            Event::DealScored { score } => {
+                for (seat, points) in score.scores.iter().enumerate() {
+                    let player = name(&seat);
+                    if let Some((_, total)) = self.scores.iter_mut().find(|(n, _)| *n == player) {
+                        *total += points;
+                    }
+                }
+                let result = text(match score.contract_won {
+                    true => Messages::Won,
+                    false => Messages::Lost,
+                });
+                fill(
+                    Messages::LogDealScored,
+                    &[&score.bid, &result, &score.taker_points],
+                )
+            }
+        };
+        self.log.push(line);
+        if self.log.len() > LOG_SIZE {
+            self.log.remove(0);
+        }
+    }
+
+    // The cursor moves over the whole hand when choosing a card, else over the legal actions
+    pub fn choices(view: &SeatView) -> Vec<Action> {
+        match view.phase {
+            Phase::Ecart => sorted(&view.hand)
+                .into_iter()
+                .map(Action::Discard)
+                .collect(),
+            Phase::Play => sorted(&view.hand).into_iter().map(Action::Play).collect(),
+            _ => view.legal_actions.clone(),
+        }
+    }
+
+    pub fn move_cursor(&mut self, view: &SeatView, delta: isize) {
+        let len = Self::choices(view).len() as isize;
+        if len > 0 {
+            self.cursor = (self.cursor as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    pub fn selected(&self, view: &SeatView) -> Option<Action> {
+        Self::choices(view)
+            .get(self.cursor)
+            .copied()
+            .filter(|action| view.legal_actions.contains(action))
+    }
+}
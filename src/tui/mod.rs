@@ -0,0 +1,187 @@
+pub mod app;
+pub mod screen;
+
+use std::io::{stdout, Stdout};
+use std::sync::{Arc, Mutex};
+use std::thread::sleep;
+use std::time::Duration;
+
+use crossterm::{
+    cursor::{Hide, Show},
+    event::{self, Event as TerminalEvent, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::cli::options::Options;
+use crate::common::{
+    action::Action,
+    deal::{Deal, DealActions},
+    error::TarotError,
+    event::EventLog,
+    game::{Game, GameActions},
+    locale::{text, Messages},
+    phase::Phase,
+    player::PlayerActions,
+    profile::{humans, record_deal, record_game},
+};
+use app::App;
+
+const BOT_DELAY: Duration = Duration::from_millis(400);
+
+enum Keys {
+    Move(isize),
+    Choose,
+    Hint,
+    Quit,
+}
+
+// Restores the terminal even when the game stops on an error
+struct Terminal {
+    out: Stdout,
+}
+impl Terminal {
+    fn enter() -> Result<Self, TarotError> {
+        let mut out = stdout();
+        enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        Ok(Terminal { out })
+    }
+}
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(self.out, LeaveAlternateScreen, Show);
+        let _ = disable_raw_mode();
+    }
+}
+
+// A full-screen front end driving the same deal state machine as the line mode
+pub fn run(options: &Options) -> Result<(), TarotError> {
+    let mut game = Game::new(options.n_players());
+    options.apply(&mut game.players)?;
+    let log = Arc::new(Mutex::new(EventLog::default()));
+    game.events.subscribe(log.clone());
+    let mut terminal = Terminal::enter()?;
+    let mut app = App::default();
+
+    let mut scored = false;
+    'deals: loop {
+        game.split_deck()?;
+        game.update_dealer()?;
+        game.reorder_players()?;
+        let mut deal = game.new_deal()?;
+        app.seat = deal.players.iter().position(|p| p.is_human).unwrap_or(0);
+        if !play_deal(&mut terminal, &mut app, &mut deal, &log)? {
+            break;
+        }
+        scored |= deal.score.is_some();
+        app.message = Some(String::from(text(match deal.taker {
+            None => Messages::ScreenNobodyBid,
+            Some(_) => Messages::ScreenNextDeal,
+        })));
+        loop {
+            refresh(&mut terminal, &mut app, &deal, &log)?;
+            match read_key()? {
+                Keys::Choose => break,
+                Keys::Quit => break 'deals,
+                _ => {}
+            }
+        }
+        app.message = None;
+        game.collect_deck(&deal)?;
+    }
+    if scored {
+        record_game(&humans(&game.players));
+    }
+    Ok(())
+}
+
+// Returns false when the player quits in the middle of the deal
+fn play_deal(
+    terminal: &mut Terminal,
+    app: &mut App,
+    deal: &mut Deal,
+    log: &Arc<Mutex<EventLog>>,
+) -> Result<bool, TarotError> {
+    while !matches!(deal.phase, Phase::Scoring | Phase::Done) {
+        refresh(terminal, app, deal, log)?;
+        let action = match deal.current_player().is_human {
+            true => match choose(terminal, app, deal, log)? {
+                Some(action) => action,
+                None => return Ok(false),
+            },
+            false => {
+                sleep(BOT_DELAY);
+                deal.current_player().act(&deal.view(deal.current))?
+            }
+        };
+        deal.apply(action)?;
+    }
+    if deal.phase == Phase::Scoring {
+        deal.compute_score()?;
+        record_deal(deal);
+    }
+    Ok(true)
+}
+
+fn choose(
+    terminal: &mut Terminal,
+    app: &mut App,
+    deal: &Deal,
+    log: &Arc<Mutex<EventLog>>,
+) -> Result<Option<Action>, TarotError> {
+    let view = deal.view(deal.current);
+    app.cursor = App::choices(&view)
+        .iter()
+        .position(|action| view.legal_actions.contains(action))
+        .unwrap_or_default();
+    loop {
+        refresh(terminal, app, deal, log)?;
+        match read_key()? {
+            Keys::Move(delta) => app.move_cursor(&view, delta),
+            Keys::Hint => app.message = deal.current_player().hint(&view),
+            Keys::Quit => return Ok(None),
+            Keys::Choose => {
+                if let Some(action) = app.selected(&view) {
+                    app.message = None;
+                    return Ok(Some(action));
+                }
+                app.message = Some(String::from(text(Messages::ScreenIllegalCard)));
+            }
+        }
+    }
+}
+
+fn refresh(
+    terminal: &mut Terminal,
+    app: &mut App,
+    deal: &Deal,
+    log: &Arc<Mutex<EventLog>>,
+) -> Result<(), TarotError> {
+    if let Ok(mut log) = log.lock() {
+        for event in log.events.drain(..) {
+            app.record(&event);
+        }
+    }
+    screen::draw(&mut terminal.out, &deal.view(app.seat), app)?;
+    Ok(())
+}
+
+fn read_key() -> Result<Keys, TarotError> {
+    loop {
+        let TerminalEvent::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Left | KeyCode::Up => return Ok(Keys::Move(-1)),
+            KeyCode::Right | KeyCode::Down => return Ok(Keys::Move(1)),
+            KeyCode::Enter | KeyCode::Char(' ') => return Ok(Keys::Choose),
+            KeyCode::Char('?') => return Ok(Keys::Hint),
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Keys::Quit),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::io::{Result, Write};
+
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Attribute, Print, SetAttribute},
+    terminal::{Clear, ClearType},
+};
+
+use super::app::{label, labels, sorted, App};
+use crate::common::{
+    action::Action,
+    card::{Card, CardSuits},
+    locale::{fill, text, Messages},
+    phase::Phase,
+    trick::{Trick, TrickGetters},
+    view::SeatView,
+};
+
+const PANEL_COLUMN: u16 = 62;
+const HAND_ROW: u16 = 17;
+const MENU_ROW: u16 = 19;
+const MESSAGE_ROW: u16 = 21;
+const HELP_ROW: u16 = 23;
+
+// Where a seat sits around the table, as seen by the player at the bottom
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Positions {
+    Bottom,
+    Left,
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+}
+impl Positions {
+    // Row and column of the name, then of the card played in the trick
+    fn coordinates(&self) -> ((u16, u16), (u16, u16)) {
+        match self {
+            Self::Bottom => ((14, 24), (11, 26)),
+            Self::Left => ((8, 1), (8, 14)),
+            Self::TopLeft => ((2, 8), (5, 18)),
+            Self::Top => ((2, 24), (5, 26)),
+            Self::TopRight => ((2, 38), (5, 34)),
+            Self::Right => ((8, 46), (8, 38)),
+        }
+    }
+}
+
+// The other seats follow the playing order around the table
+pub fn positions(n_players: usize, seat: usize) -> Vec<(usize, Positions)> {
+    let layout: &[Positions] = match n_players {
+        3 => &[Positions::Bottom, Positions::Left, Positions::Right],
+        4 => &[
+            Positions::Bottom,
+            Positions::Left,
+            Positions::Top,
+            Positions::Right,
+        ],
+        _ => &[
+            Positions::Bottom,
+            Positions::Left,
+            Positions::TopLeft,
+            Positions::TopRight,
+            Positions::Right,
+        ],
+    };
+    layout
+        .iter()
+        .enumerate()
+        .map(|(offset, position)| ((seat + offset) % n_players, *position))
+        .collect()
+}
+
+pub fn draw<W: Write>(out: &mut W, view: &SeatView, app: &App) -> Result<()> {
+    queue!(out, Clear(ClearType::All))?;
+    draw_header(out, view, app)?;
+    draw_table(out, view)?;
+    draw_hand(out, view, app)?;
+    draw_menu(out, view, app)?;
+    draw_panel(out, view, app)?;
+    if let Some(message) = &app.message {
+        queue!(out, MoveTo(0, MESSAGE_ROW), Print(message))?;
+    }
+    queue!(out, MoveTo(0, HELP_ROW), Print(text(Messages::ScreenHelp)))?;
+    out.flush()
+}
+
+fn draw_header<W: Write>(out: &mut W, view: &SeatView, app: &App) -> Result<()> {
+    let mut header = fill(Messages::ScreenDeal, &[&app.deals]);
+    if let Some(taker) = &view.taker {
+        header += &fill(
+            Messages::ScreenTaker,
+            &[&taker.bid, &view.players[taker.seat]],
+        );
+    }
+    if let Some(king) = &view.called_king {
+        header += &fill(Messages::ScreenCalling, &[&label(king)]);
+    }
+    queue!(out, MoveTo(0, 0), Print(header))
+}
+
+// The trick in progress, or the last one until somebody plays again
+fn shown_trick(view: &SeatView) -> Option<&Trick> {
+    match (view.trick.played_cards.is_empty(), view.phase) {
+        (false, _) => Some(&view.trick),
+        (true, Phase::Play | Phase::Scoring | Phase::Done) => view.tricks.last(),
+        _ => None,
+    }
+}
+
+fn draw_table<W: Write>(out: &mut W, view: &SeatView) -> Result<()> {
+    let n_players = view.n_players();
+    let trick = shown_trick(view);
+    for (seat, position) in positions(n_players, view.seat) {
+        let ((row, column), (card_row, card_column)) = position.coordinates();
+        let mut name = view.players[seat].clone();
+        if view.current == seat && !matches!(view.phase, Phase::Scoring | Phase::Done) {
+            name = format!("> {}", name);
+        }
+        queue!(out, MoveTo(column, row), Print(name))?;
+
+        let card = trick.and_then(|trick| {
+            let index = (seat + n_players - trick.leader) % n_players;
+            trick.played_cards.get(index)
+        });
+        if let Some(card) = card {
+            queue!(out, MoveTo(card_column, card_row), Print(label(card)))?;
+        }
+    }
+    let winner = trick
+        .filter(|trick| trick.played_cards.len() == n_players)
+        .and_then(|trick| trick.winner(n_players));
+    if let Some(winner) = winner {
+        let winner = fill(Messages::ScreenTrickWinner, &[&view.players[winner]]);
+        queue!(out, MoveTo(18, 12), Print(winner))?;
+    }
+    Ok(())
+}
+
+fn draw_hand<W: Write>(out: &mut W, view: &SeatView, app: &App) -> Result<()> {
+    queue!(out, MoveTo(0, HAND_ROW), Print(text(Messages::ScreenHand)))?;
+    let choosing = view.is_turn() && matches!(view.phase, Phase::Ecart | Phase::Play);
+    let legal = view.legal_cards();
+    let mut column = 11;
+    let mut suit: Option<CardSuits> = None;
+    for (index, card) in sorted(&view.hand).iter().enumerate() {
+        if suit.is_some_and(|suit| suit != card.suit.name) {
+            column += 2;
+        }
+        suit = Some(card.suit.name);
+        let text = label(card);
+        let width = text.chars().count() as u16 + 1;
+        queue!(out, MoveTo(column, HAND_ROW))?;
+        draw_card(
+            out,
+            card,
+            choosing && index == app.cursor,
+            choosing && !legal.contains(card),
+        )?;
+        column += width;
+    }
+    Ok(())
+}
+
+fn draw_card<W: Write>(out: &mut W, card: &Card, selected: bool, disabled: bool) -> Result<()> {
+    if selected {
+        queue!(out, SetAttribute(Attribute::Reverse))?;
+    }
+    if disabled {
+        queue!(out, SetAttribute(Attribute::Dim))?;
+    }
+    queue!(out, Print(label(card)), SetAttribute(Attribute::Reset))
+}
+
+fn draw_menu<W: Write>(out: &mut W, view: &SeatView, app: &App) -> Result<()> {
+    if !view.is_turn() || matches!(view.phase, Phase::Ecart | Phase::Play) {
+        return Ok(());
+    }
+    queue!(out, MoveTo(0, MENU_ROW))?;
+    for (index, action) in App::choices(view).iter().enumerate() {
+        let choice = match action {
+            Action::Bid(bid) => bid.to_string(),
+            Action::CallKing(card) => label(card),
+            Action::Declare(Some(handle)) => handle.label().to_string(),
+            Action::Declare(None) => String::from(text(Messages::NoHandle)),
+            Action::Discard(card) | Action::Play(card) => label(card),
+        };
+        if index == app.cursor {
+            queue!(out, SetAttribute(Attribute::Reverse))?;
+        }
+        queue!(
+            out,
+            Print(choice),
+            SetAttribute(Attribute::Reset),
+            Print("  ")
+        )?;
+    }
+    Ok(())
+}
+
+fn draw_panel<W: Write>(out: &mut W, view: &SeatView, app: &App) -> Result<()> {
+    let mut row = 0;
+    let mut line = |out: &mut W, text: String| -> Result<()> {
+        queue!(out, MoveTo(PANEL_COLUMN, row), Print(text))?;
+        row += 1;
+        Ok(())
+    };
+    line(out, String::from(text(Messages::ScreenScores)))?;
+    for (name, total) in &app.scores {
+        line(out, format!("  {:<12}{:>6}", name, total))?;
+    }
+    line(out, String::new())?;
+    line(out, String::from(text(Messages::ScreenAuction)))?;
+    for (seat, bid) in &view.auction {
+        line(out, format!("  {}: {}", view.players[*seat], bid))?;
+    }
+    if let Some(kitty) = &view.kitty {
+        line(
+            out,
+            format!("  {}", fill(Messages::ScreenKitty, &[&labels(kitty)])),
+        )?;
+    }
+    line(out, String::new())?;
+    line(out, String::from(text(Messages::ScreenEvents)))?;
+    for event in &app.log {
+        line(out, format!("  {}", event))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod advisor {
+    use tarot_cli::{
+        bots::advisor::{advise, explain},
+        common::{
+            action::Action,
+            bid::Bids,
+            card::{Card, CardSuits},
+            deal::Deal,
+            game::Game,
+            phase::Phase,
+            player::PlayerActions,
+        },
+    };
+
+    #[test]
+    fn advice_matches_the_bot_play() {
+        let mut game = Game::default();
+        for player in game.players.iter_mut() {
+            player.is_human = false;
+        }
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        while deal.phase != Phase::Done && deal.phase != Phase::Scoring {
+            let view = deal.view(deal.current);
+            let mut human = deal.current_player().clone();
+            human.is_human = true;
+            let hint = advise(&human, &view).unwrap();
+            let action = deal.current_player().act(&view).unwrap();
+            assert_eq!(hint.action, action);
+            assert!(!hint.reason.is_empty());
+            deal.apply(action).unwrap();
+        }
+    }
+
+    #[test]
+    fn explains_bids_with_the_hand() {
+        let mut game = Game::default();
+        let deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        let view = deal.view(0);
+        let reason = explain(&view, &Action::Bid(Bids::Garde));
+        let trumps = view
+            .hand
+            .iter()
+            .filter(|card| card.suit.name == CardSuits::Trumps && card.rank != 22)
+            .count();
+        assert!(reason.contains(&format!("{} trumps", trumps)));
+        assert!(reason.contains("oudlers"));
+    }
+
+    #[test]
+    fn explains_a_void_in_the_ecart() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        let card = Card::new(3, CardSuits::Hearts);
+        deal.players[0].hand.cards = vec![card, Card::new(4, CardSuits::Spades)];
+        let hint = explain(&deal.view(0), &Action::Discard(card));
+        assert_eq!(hint, "it is your last Hearts, discarding it leaves a void");
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod analysis {
+    use tarot_cli::{
+        bots::analysis::{analyze, Mistakes},
+        common::{
+            bid::Bids,
+            deal::{Deal, DealActions},
+            evaluation::ideal_bid,
+            game::Game,
+            phase::Phase,
+            score::multiplier,
+        },
+    };
+
+    use crate::common::play_until;
+
+    // Every seat takes the first legal action, bidding up to a Garde Contre
+    fn first_choice_deal() -> Deal {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| deal.phase == Phase::Scoring);
+        deal
+    }
+
+    #[test]
+    fn flags_overbids_against_the_evaluator() {
+        let deal = first_choice_deal();
+        let hand = &deal.record.hands[3];
+        let ideal = ideal_bid(hand, 4);
+        let mistakes = analyze(&deal.record, &[3]).unwrap();
+
+        let overbid = mistakes.iter().find(|m| m.kind == Mistakes::Overbid);
+        match ideal {
+            Bids::GardeContre => assert!(overbid.is_none()),
+            _ => {
+                let overbid = overbid.unwrap();
+                assert_eq!(overbid.seat, 3);
+                let expected = (multiplier(&Bids::GardeContre) - multiplier(&ideal)) * 25.0;
+                assert_eq!(overbid.cost, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn only_reviews_the_given_seats() {
+        let deal = first_choice_deal();
+        let mistakes = analyze(&deal.record, &[1, 2]).unwrap();
+        assert!(mistakes.iter().all(|m| m.seat == 1 || m.seat == 2));
+        assert!(mistakes.iter().all(|m| m.cost > 0.0));
+        assert_eq!(analyze(&deal.record, &[]).unwrap(), []);
+    }
+
+    #[test]
+    fn solved_plays_cost_at_least_two_points() {
+        let deal = first_choice_deal();
+        let mistakes = analyze(&deal.record, &[0, 1, 2, 3]).unwrap();
+        for mistake in mistakes.iter().filter(|m| m.kind == Mistakes::Play) {
+            assert!(mistake.cost >= 2.0, "{}", mistake);
+        }
+    }
+
+    #[test]
+    fn reviews_a_deal_played_by_bots() {
+        let mut game = Game::default();
+        for player in game.players.iter_mut() {
+            player.is_human = false;
+        }
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        deal.take_bids().unwrap();
+        if deal.taker.is_none() {
+            return;
+        }
+        deal.call_king().unwrap();
+        deal.compose_kitty().unwrap();
+        deal.declare_handles().unwrap();
+        deal.play_tricks().unwrap();
+        let mistakes = analyze(&deal.record, &[0, 1, 2, 3]).unwrap();
+        // The bots bid like the evaluator and compose the reference écart
+        assert!(mistakes
+            .iter()
+            .all(|m| !matches!(m.kind, Mistakes::Overbid | Mistakes::Ecart)));
+    }
+}
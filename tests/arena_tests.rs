@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod arena {
+    use rstest::rstest;
+    use tarot_cli::{
+        bots::difficulty::Difficulties,
+        cli::options::{Commands, Options},
+        common::arena::{arena, ArenaConfig},
+    };
+
+    fn config(boards: usize, threads: usize) -> ArenaConfig {
+        ArenaConfig {
+            boards,
+            threads,
+            seed: Some(5),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(2, 4, [[0, 1, 0, 1], [1, 0, 1, 0]].concat())]
+    #[case(3, 3, [[0, 1, 2], [1, 2, 0], [2, 0, 1]].concat())]
+    #[case(4, 3, [[0, 1, 2], [1, 2, 3], [2, 3, 0], [3, 0, 1]].concat())]
+    fn rotates_every_strategy_through_every_seat(
+        #[case] n_strategies: usize,
+        #[case] n_players: u8,
+        #[case] expected: Vec<usize>,
+    ) {
+        let config = ArenaConfig {
+            n_players,
+            strategies: vec![Difficulties::Beginner; n_strategies],
+            ..Default::default()
+        };
+        assert!(config.is_balanced());
+        let seatings: Vec<usize> = (0..config.n_seatings())
+            .flat_map(|rotation| config.seating(rotation))
+            .collect();
+        assert_eq!(seatings, expected);
+    }
+
+    #[test]
+    fn reports_every_strategy() {
+        let report = arena(&config(30, 2));
+        assert_eq!(report.failures, []);
+        assert_eq!((report.boards, report.seatings), (30, 2));
+        assert_eq!(report.standings.len(), 2);
+        // Both strategies play the same deals, what one wins the other loses
+        let means: f64 = report.standings.iter().map(|s| s.mean).sum();
+        let ratings: f64 = report.standings.iter().map(|s| s.rating).sum();
+        assert!(means.abs() < 1e-9);
+        assert!((ratings - 3000.0).abs() < 1e-6);
+        assert!(report.standings.iter().all(|s| s.margin >= 0.0));
+        assert!(report.standings[0].mean >= report.standings[1].mean);
+    }
+
+    #[test]
+    fn seeded_arenas_do_not_depend_on_threads() {
+        assert_eq!(arena(&config(12, 1)), arena(&config(12, 3)));
+    }
+
+    #[test]
+    fn parses_the_arena_command() {
+        let args = "arena --strategies beginner,expert,intermediate --deals 10 --players 3";
+        let options = Options::parse(args.split(' ').map(String::from)).unwrap();
+        assert_eq!(options.command, Commands::Arena);
+
+        let config = options.arena().unwrap();
+        assert_eq!(config.boards, 10);
+        assert_eq!(config.n_players, 3);
+        assert_eq!(
+            config.strategies,
+            [
+                Difficulties::Beginner,
+                Difficulties::Expert,
+                Difficulties::Intermediate
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("arena --strategies expert")]
+    #[case("arena --strategies expert,genius")]
+    #[case("arena --strategies beginner,expert,intermediate --players 4")]
+    #[case("arena --strategies beginner,expert --players 5")]
+    fn rejects_invalid_arenas(#[case] args: &str) {
+        let options = Options::parse(args.split(' ').map(String::from));
+        assert!(options.and_then(|options| options.arena()).is_err());
+    }
+}
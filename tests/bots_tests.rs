@@ -0,0 +1,223 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod bots {
+    use std::time::{Duration, Instant};
+
+    use rand::thread_rng;
+    use rstest::rstest;
+    use tarot_cli::{
+        bots::{
+            difficulty::Difficulties,
+            ismcts::{search, Budget, SearchConfig},
+            sampler::determinize,
+            tracker::played_cards,
+        },
+        cli::options::{Options, Seats},
+        common::{
+            action::Action,
+            bid::Bids,
+            card::CardGetters,
+            deal::{Deal, DealActions},
+            game::Game,
+            phase::Phase,
+            trick::TrickGetters,
+        },
+    };
+
+    use crate::common::play_until;
+
+    #[rstest]
+    fn parses_difficulties(
+        #[values(
+            ("beginner", Difficulties::Beginner),
+            ("Intermediate", Difficulties::Intermediate),
+            ("EXPERT", Difficulties::Expert)
+        )]
+        case: (&str, Difficulties),
+    ) {
+        let (name, expected) = case;
+        assert_eq!(name.parse::<Difficulties>(), Ok(expected));
+    }
+
+    #[test]
+    fn configures_seats_from_the_command_line() {
+        let args = ["--seat", "1=expert", "--seat", "3=human"].map(String::from);
+        let options = Options::parse(args).unwrap();
+        assert_eq!(
+            options.seats,
+            [(1, Seats::Bot(Difficulties::Expert)), (3, Seats::Human)]
+        );
+
+        let mut game = Game::default();
+        options.apply(&mut game.players).unwrap();
+        assert!(!game.players[0].is_human);
+        assert_eq!(game.players[0].level, Difficulties::Expert);
+        assert!(game.players[2].is_human);
+    }
+
+    #[rstest]
+    fn rejects_invalid_seats(#[values("1", "x=expert", "1=god", "9=expert")] seat: &str) {
+        let args = ["--seat", seat].map(String::from);
+        let result = Options::parse(args).and_then(|options| {
+            let mut game = Game::default();
+            options.apply(&mut game.players)
+        });
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn plays_a_complete_deal_at_every_level(
+        #[values(
+            Difficulties::Beginner,
+            Difficulties::Intermediate,
+            Difficulties::Expert
+        )]
+        level: Difficulties,
+    ) {
+        let mut game = Game::default();
+        for player in game.players.iter_mut() {
+            player.is_human = false;
+        }
+        game.players[0].level = level;
+        game.players[0].search.budget = Budget::Iterations(20);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        deal.apply(Action::Bid(Bids::Garde)).unwrap();
+        deal.take_bids().unwrap();
+        deal.compose_kitty().unwrap();
+        deal.declare_handles().unwrap();
+        deal.play_tricks().unwrap();
+        deal.compute_score().unwrap();
+        assert_eq!(deal.phase, Phase::Done);
+    }
+
+    #[rstest]
+    fn samples_deals_consistent_with_the_view(#[values(3, 4, 5)] n_players: u8) {
+        let mut game = Game::new(n_players);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| deal.tricks.len() >= 2);
+        let view = deal.view(deal.current);
+
+        let sample = determinize(&view, &mut thread_rng()).unwrap();
+        assert_eq!(sample.phase, Phase::Play);
+        assert_eq!(sample.current, view.seat);
+        assert_eq!(sample.view(view.seat).hand, view.hand);
+        assert_eq!(sample.trick, view.trick);
+        assert_eq!(sample.legal_actions(), view.legal_actions);
+    }
+
+    #[test]
+    fn expert_plays_a_legal_card() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| !deal.tricks.is_empty());
+        let view = deal.view(deal.current);
+        let config = SearchConfig {
+            budget: Budget::Iterations(30),
+            seed: None,
+        };
+
+        let card = search(&view, &config).unwrap();
+        assert!(view.legal_cards().contains(&card));
+    }
+
+    #[test]
+    fn expert_is_deterministic_with_a_seed() {
+        let mut game = Game::new(5);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| deal.tricks.len() >= 3);
+        let view = deal.view(deal.current);
+        let config = SearchConfig {
+            budget: Budget::Iterations(50),
+            seed: Some(42),
+        };
+
+        let card = search(&view, &config).unwrap();
+        for _ in 0..3 {
+            assert_eq!(search(&view, &config).unwrap(), card);
+        }
+    }
+
+    #[test]
+    fn expert_stops_after_its_thinking_time() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| !deal.tricks.is_empty());
+        let view = deal.view(deal.current);
+        let config = SearchConfig {
+            budget: Budget::Time(Duration::from_millis(50)),
+            seed: None,
+        };
+
+        let start = Instant::now();
+        search(&view, &config).unwrap();
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn samples_respect_the_revealed_voids() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| deal.phase >= Phase::Play);
+        // Play until somebody does not follow the suit of an other suit than trumps
+        let void = loop {
+            let action = deal.legal_actions()[0];
+            let suit = deal.trick.played_suit();
+            let seat = deal.current;
+            deal.apply(action).unwrap();
+            if deal.phase != Phase::Play {
+                return;
+            }
+            match (suit, action) {
+                (Some(suit), Action::Play(card)) if card.suit.name != suit && !card.is_fool() => {
+                    break (seat, suit)
+                }
+                _ => {}
+            }
+        };
+        let (void_seat, void_suit) = void;
+        let observer = (void_seat + 1) % 4;
+        while deal.current != observer {
+            let action = deal.legal_actions()[0];
+            deal.apply(action).unwrap();
+            if deal.phase != Phase::Play {
+                return;
+            }
+        }
+
+        let view = deal.view(observer);
+        for _ in 0..20 {
+            let sample = determinize(&view, &mut thread_rng()).unwrap();
+            assert!(sample.players[void_seat]
+                .hand
+                .cards
+                .iter()
+                .all(|card| card.suit.name != void_suit || card.is_fool()));
+        }
+    }
+
+    #[test]
+    fn samples_give_the_revealed_kitty_to_the_taker() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        for bid in [Bids::Garde, Bids::Passe, Bids::Passe, Bids::Passe] {
+            deal.apply(Action::Bid(bid)).unwrap();
+        }
+        play_until(&mut deal, |deal| deal.phase >= Phase::Play);
+        play_until(&mut deal, |deal| deal.current != 0);
+        let view = deal.view(deal.current);
+        let kitty = view.kitty.clone().unwrap();
+
+        for _ in 0..20 {
+            let sample = determinize(&view, &mut thread_rng()).unwrap();
+            let taker_cards = [
+                sample.players[0].hand.cards.clone(),
+                sample.kitty.cards.clone(),
+                played_cards(&view)[0].clone(),
+            ]
+            .concat();
+            assert!(kitty.iter().all(|card| taker_cards.contains(card)));
+        }
+    }
+}
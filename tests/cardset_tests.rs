@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod cardset {
+    use rand::{seq::SliceRandom, thread_rng};
+    use rstest::rstest;
+    use tarot_cli::common::{
+        action::Action,
+        card::{Card, CardSuits},
+        cardset::{allowed_moves, winning_index, CardSet},
+        deal::Deal,
+        game::{new_deck, Game},
+        trick::{allowed_cards_to_play, TrickActions, TrickGetters},
+    };
+
+    fn indexes(cards: &[Card]) -> Vec<u32> {
+        cards.iter().map(CardSet::index).collect()
+    }
+
+    #[test]
+    fn converts_every_card() {
+        let deck = new_deck();
+        let set = CardSet::from(deck.as_slice());
+        assert_eq!(set, CardSet::FULL);
+        assert_eq!(set.len(), 78);
+        for card in &deck {
+            assert_eq!(CardSet::card(CardSet::index(card)), *card);
+        }
+        assert_eq!(Vec::<Card>::from(set).len(), 78);
+    }
+
+    #[rstest]
+    fn groups_cards_by_suit(
+        #[values(
+            (CardSuits::Clubs, 14),
+            (CardSuits::Spades, 14),
+            (CardSuits::Trumps, 22)
+        )]
+        case: (CardSuits, usize),
+    ) {
+        let (suit, expected_len) = case;
+        let set = CardSet::suit(suit);
+        assert_eq!(set.len(), expected_len);
+        assert!(set.cards().iter().all(|card| card.suit.name == suit));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let king = Card::new(14, CardSuits::Hearts);
+        let fool = Card::new(22, CardSuits::Trumps);
+        let mut set = CardSet::EMPTY;
+        set.insert(&king);
+        set.insert(&fool);
+        assert!(set.contains(&king) && set.contains(&fool));
+        assert_eq!((set - CardSet::FOOL).cards(), [king]);
+        assert_eq!((set & CardSet::TRUMPS), CardSet::EMPTY);
+        assert_eq!((!set).len(), 76);
+        assert_eq!(set.highest(), Some(CardSet::index(&fool)));
+        assert_eq!(set.lowest(), Some(CardSet::index(&king)));
+        set.remove(&king);
+        assert_eq!(set, CardSet::FOOL);
+    }
+
+    #[rstest]
+    fn matches_the_card_rules_during_a_deal(#[values(3, 4, 5)] n_players: u8) {
+        let mut game = Game::new(n_players);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        let mut rng = thread_rng();
+        while let Some(action) = deal.legal_actions().choose(&mut rng).copied() {
+            if let Action::Play(_) = action {
+                let hand = &deal.current_player().hand.cards;
+                let played = indexes(&deal.trick.played_cards);
+                let mut expected = allowed_cards_to_play(&deal.trick, hand);
+                let mut moves = allowed_moves(CardSet::from(hand.as_slice()), &played).cards();
+                expected.sort_by_key(CardSet::index);
+                moves.sort_by_key(CardSet::index);
+                assert_eq!(moves, expected);
+            }
+            deal.apply(action).unwrap();
+            if let Some(trick) = deal.tricks.last() {
+                let played = indexes(&trick.played_cards);
+                assert_eq!(
+                    winning_index(&played),
+                    trick.get_best_played_card_index(trick.played_suit())
+                );
+            }
+        }
+    }
+}
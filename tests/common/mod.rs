@@ -0,0 +1,9 @@
+use tarot_cli::common::deal::Deal;
+
+// Every seat takes its first legal action until the deal is where the test wants it
+pub fn play_until(deal: &mut Deal, done: impl Fn(&Deal) -> bool) {
+    while !done(deal) {
+        let action = deal.legal_actions()[0];
+        deal.apply(action).unwrap();
+    }
+}
@@ -1,13 +1,28 @@
 #[cfg(test)]
 mod deal {
     use rstest::rstest;
-    use tarot_cli::common::{deal::Deal, game::Game};
+    use tarot_cli::common::{
+        action::Action,
+        bid::Bids,
+        card::{Card, CardSuits},
+        deal::{Deal, DealActions},
+        error::TarotError,
+        game::Game,
+        phase::Phase,
+    };
+
+    fn play_first_legal_actions(deal: &mut Deal) {
+        while let Some(action) = deal.legal_actions().first().copied() {
+            deal.apply(action).unwrap();
+        }
+        deal.compute_score().unwrap();
+    }
 
     #[rstest]
     fn deals_right_number_of_cards(#[values((4, 6, 18), (5, 3, 15))] case: (u8, usize, usize)) {
         let (n_player, expected_kitty_size, expected_n_cards_by_player) = case;
         let mut game = Game::new(n_player);
-        let deal = Deal::new(&mut game.players, &mut game.deck);
+        let deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
 
         let n_cards = deal
             .players
@@ -27,7 +42,121 @@ mod deal {
     ) {
         let (n_players, expected_max_size) = case;
         let mut game = Game::new(n_players);
-        let deal = Deal::new(&mut game.players, &mut game.deck);
+        let deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
         assert_eq!(deal.kitty.max_size, expected_max_size);
     }
+
+    #[test]
+    fn new_deal_starts_with_the_auction() {
+        let mut game = Game::default();
+        let deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        assert_eq!(deal.phase, Phase::Auction);
+        assert_eq!(deal.current, 0);
+        assert_eq!(deal.legal_actions().len(), 5);
+    }
+
+    #[test]
+    fn rejects_out_of_phase_actions() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        let action = Action::Play(Card::new(1, CardSuits::Trumps));
+        assert!(matches!(
+            deal.apply(action),
+            Err(TarotError::InvalidTransition {
+                phase: Phase::Auction,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_bids_lower_than_the_current_one() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        deal.apply(Action::Bid(Bids::Garde)).unwrap();
+        assert!(matches!(
+            deal.apply(Action::Bid(Bids::Petite)),
+            Err(TarotError::RuleViolation(Action::Bid(Bids::Petite)))
+        ));
+        assert_eq!(deal.current, 1);
+    }
+
+    #[test]
+    fn ends_the_deal_when_everybody_passes() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        for _ in 0..4 {
+            deal.apply(Action::Bid(Bids::Passe)).unwrap();
+        }
+        assert_eq!(deal.phase, Phase::Done);
+        assert!(deal.taker.is_none());
+        assert!(deal.legal_actions().is_empty());
+    }
+
+    #[test]
+    fn garde_gives_the_kitty_to_the_taker() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        for bid in [Bids::Passe, Bids::Garde, Bids::Passe, Bids::Passe] {
+            deal.apply(Action::Bid(bid)).unwrap();
+        }
+        assert_eq!(deal.phase, Phase::Ecart);
+        assert_eq!(deal.current, 1);
+        assert_eq!(deal.players[1].hand.cards.len(), 24);
+        for action in deal.legal_actions() {
+            assert!(matches!(action, Action::Discard(card) if card.rank != 14));
+        }
+    }
+
+    #[rstest]
+    fn plays_a_complete_deal(#[values(3, 4, 5)] n_players: u8) {
+        let mut game = Game::new(n_players);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_first_legal_actions(&mut deal);
+
+        assert_eq!(deal.phase, Phase::Done);
+        let won_cards = deal
+            .players
+            .iter()
+            .fold(0, |acc, player| acc + player.hand.won_cards.len());
+        assert_eq!(won_cards + deal.kitty.cards.len(), 78);
+        let score = deal.score.unwrap();
+        assert_eq!(score.scores.iter().sum::<f64>(), 0.0);
+    }
+
+    #[test]
+    fn replays_a_recorded_deal() {
+        let mut game = Game::new(5);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_first_legal_actions(&mut deal);
+
+        let mut replayed = Deal::from_record(&deal.record).unwrap();
+        replayed.compute_score().unwrap();
+        assert_eq!(replayed.record, deal.record);
+        assert_eq!(replayed.score, deal.score);
+    }
+
+    #[test]
+    fn rejects_an_incomplete_deck() {
+        let mut game = Game::default();
+        game.deck.pop();
+        assert!(matches!(
+            Deal::new(&mut game.players, &mut game.deck),
+            Err(TarotError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_steps_called_before_their_phase() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        assert!(matches!(
+            deal.play_tricks(),
+            Err(TarotError::InvalidPhase {
+                expected: Phase::Play,
+                found: Phase::Auction
+            })
+        ));
+        assert!(deal.compute_score().is_err());
+    }
 }
@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod engine {
+    use std::env;
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    use rstest::rstest;
+    use tarot_cli::{
+        bots::engine::{parse_answer, position, Engine},
+        cli::options::{Options, Seats},
+        common::{
+            action::Action,
+            bid::Bids,
+            card::{Card, CardSuits},
+            deal::Deal,
+            game::Game,
+            handle::Handles,
+            phase::Phase,
+            player::PlayerActions,
+            view::SeatView,
+        },
+    };
+
+    // Always answers the first legal choice
+    const FIRST: &str = r#"while read -r command kind choice rest; do
+  case "$command" in
+    tarot) echo "id name First"; echo "info ready"; echo tarotok ;;
+    go) echo "$kind $choice" ;;
+    quit) exit 0 ;;
+  esac
+done"#;
+
+    // Never answers a legal card
+    const STUBBORN: &str = r#"while read -r command rest; do
+  case "$command" in
+    tarot) echo tarotok ;;
+    go) echo "play Z1" ;;
+  esac
+done"#;
+
+    // Introduces itself, then never answers
+    const SILENT: &str = r#"while read -r command rest; do
+  case "$command" in
+    tarot) echo tarotok ;;
+  esac
+done"#;
+
+    // Introduces itself, then ignores its input
+    const DEAF: &str = r#"read -r command rest
+echo tarotok
+exec sleep 60"#;
+
+    fn script(name: &str, body: &str) -> String {
+        let path = env::temp_dir().join(format!("tarot-{}-{}.sh", name, std::process::id()));
+        fs::write(&path, body).unwrap();
+        format!("sh {}", path.display())
+    }
+
+    fn view(phase: Phase) -> SeatView {
+        SeatView {
+            phase,
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(Phase::Auction, "bid GardeSans", Action::Bid(Bids::GardeSans))]
+    #[case(
+        Phase::KingCall,
+        "call H14",
+        Action::CallKing(Card::new(14, CardSuits::Hearts))
+    )]
+    #[case(
+        Phase::Ecart,
+        "discard C3",
+        Action::Discard(Card::new(3, CardSuits::Clubs))
+    )]
+    #[case(Phase::Declarations, "declare none", Action::Declare(None))]
+    #[case(
+        Phase::Declarations,
+        "declare Double",
+        Action::Declare(Some(Handles::Double))
+    )]
+    #[case(
+        Phase::Play,
+        "play T21",
+        Action::Play(Card::new(21, CardSuits::Trumps))
+    )]
+    fn reads_the_answers(#[case] phase: Phase, #[case] line: &str, #[case] expected: Action) {
+        assert_eq!(parse_answer(line, &view(phase)), Ok(expected));
+    }
+
+    #[rstest]
+    #[case(Phase::Play, "bid Garde")]
+    #[case(Phase::Play, "play")]
+    #[case(Phase::Auction, "bid Everything")]
+    #[case(Phase::Declarations, "declare Quadruple")]
+    fn refuses_malformed_answers(#[case] phase: Phase, #[case] line: &str) {
+        assert!(parse_answer(line, &view(phase)).is_err());
+    }
+
+    #[test]
+    fn describes_the_position() {
+        let mut game = Game::default();
+        let deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        let lines = position(&deal.view(0));
+        let lines: Vec<&str> = lines.lines().collect();
+
+        assert_eq!(lines[0], "position");
+        assert_eq!(lines[1], "seat 0");
+        assert_eq!(
+            lines[3].split(' ').count(),
+            1 + deal.players[0].hand.cards.len()
+        );
+        assert_eq!(lines[4], "auction");
+        assert_eq!(
+            lines.last(),
+            Some(&"go bid Petite Garde GardeSans GardeContre Passe")
+        );
+    }
+
+    #[test]
+    fn plays_a_deal_against_the_bots() {
+        let seat = format!("2=engine:{}", script("first", FIRST));
+        let options = Options::parse(["--seat".to_string(), seat]).unwrap();
+        assert!(matches!(options.seats[0], (2, Seats::Engine(_))));
+        assert!(options.simulation().is_err());
+
+        let mut game = Game::default();
+        for player in game.players.iter_mut() {
+            player.is_human = false;
+        }
+        options.apply(&mut game.players).unwrap();
+        assert_eq!(game.players[1].name, "First");
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        while !matches!(deal.phase, Phase::Scoring | Phase::Done) {
+            let action = deal.current_player().act(&deal.view(deal.current)).unwrap();
+            deal.apply(action).unwrap();
+        }
+    }
+
+    #[test]
+    fn gives_up_on_an_engine_without_legal_answers() {
+        let mut engine = Engine::launch(&script("stubborn", STUBBORN)).unwrap();
+        let mut game = Game::default();
+        let deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        assert!(engine.ask(&deal.view(0)).is_err());
+        assert!(Engine::launch("").is_err());
+    }
+
+    #[test]
+    fn gives_up_on_an_engine_that_does_not_answer() {
+        let timeout = Duration::from_millis(200);
+        let mute = script("mute", "while read -r line; do :; done");
+        assert!(Engine::launch_with_timeout(&mute, timeout).is_err());
+
+        let mut engine = Engine::launch_with_timeout(&script("silent", SILENT), timeout).unwrap();
+        let mut game = Game::default();
+        let deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        let start = Instant::now();
+        assert!(engine.ask(&deal.view(0)).is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn stops_an_engine_that_does_not_quit() {
+        let timeout = Duration::from_millis(200);
+        let engine = Engine::launch_with_timeout(&script("deaf", DEAF), timeout).unwrap();
+        let start = Instant::now();
+        drop(engine);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod evaluation {
+    use rstest::rstest;
+    use tarot_cli::common::{
+        bid::Bids,
+        card::Card,
+        evaluation::{evaluate_hand, ideal_bid},
+    };
+
+    const WEAK: &str = "T2 T5 T8 C3 C5 C7 C9 D2 D4 D6 D10 D11 H1 H3 H8 S2 S4 S6";
+    const PETITE: &str = "T3 T7 T10 T12 T16 T21 T22 C14 C13 C5 D14 D2 D3 H10 H9 H4 S1 S5";
+    const GARDE: &str = "T1 T4 T6 T9 T13 T17 T21 T22 C14 C13 C2 D14 D8 H12 H11 H5 S3 S6";
+    const GARDE_SANS: &str = "T1 T3 T5 T8 T15 T18 T19 T20 T21 T22 C14 C13 C12 D14 S14 S2 S4 S7";
+    const GARDE_CONTRE: &str =
+        "T1 T6 T9 T11 T14 T16 T17 T18 T19 T20 T21 T22 C14 C13 D14 D13 H14 H2";
+    const NO_OUDLER: &str = "T10 T12 T15 T16 T17 T18 T19 T20 C14 C13 C2 D14 D13 D2 H14 H13 S14 S13";
+
+    fn hand(ids: &str) -> Vec<Card> {
+        ids.split_whitespace()
+            .map(|id| id.parse().unwrap())
+            .collect()
+    }
+
+    #[rstest]
+    fn evaluates_reference_hands(
+        #[values(
+            (WEAK, 12.0),
+            (PETITE, 45.0),
+            (GARDE, 57.0),
+            (GARDE_SANS, 75.0),
+            (GARDE_CONTRE, 82.0),
+            (NO_OUDLER, 62.0)
+        )]
+        case: (&str, f64),
+    ) {
+        let (ids, expected_points) = case;
+        assert_eq!(evaluate_hand(&hand(ids)), expected_points);
+    }
+
+    #[rstest]
+    fn bids_reference_hands_with_four_players(
+        #[values(
+            (WEAK, Bids::Passe),
+            (PETITE, Bids::Petite),
+            (GARDE, Bids::Garde),
+            (GARDE_SANS, Bids::GardeSans),
+            (GARDE_CONTRE, Bids::GardeContre),
+            (NO_OUDLER, Bids::Garde)
+        )]
+        case: (&str, Bids),
+    ) {
+        let (ids, expected_bid) = case;
+        assert_eq!(ideal_bid(&hand(ids), 4), expected_bid);
+    }
+
+    #[rstest]
+    fn adapts_thresholds_to_the_number_of_players(
+        #[values(
+            (PETITE, 3, Bids::Passe),
+            (PETITE, 5, Bids::Petite),
+            (GARDE, 3, Bids::Petite),
+            (GARDE_SANS, 5, Bids::GardeContre),
+            (GARDE_CONTRE, 3, Bids::Garde)
+        )]
+        case: (&str, usize, Bids),
+    ) {
+        let (ids, n_players, expected_bid) = case;
+        assert_eq!(ideal_bid(&hand(ids), n_players), expected_bid);
+    }
+
+    #[test]
+    fn unprotected_petit_is_worth_nothing() {
+        let protected = hand("T1 T2 T3 T4 T5 T6");
+        let unprotected = hand("T1 T2 T3");
+        assert_eq!(evaluate_hand(&unprotected), evaluate_hand(&hand("T2 T3")));
+        assert!(evaluate_hand(&protected) > evaluate_hand(&hand("T2 T3 T4 T5 T6")));
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod event {
+    use std::sync::{Arc, Mutex};
+
+    use rstest::rstest;
+    use tarot_cli::common::{
+        deal::{Deal, DealActions},
+        event::{Event, EventLog},
+        game::{Game, GameActions},
+    };
+
+    fn count(log: &EventLog, predicate: fn(&Event) -> bool) -> usize {
+        log.events.iter().filter(|event| predicate(event)).count()
+    }
+
+    #[rstest]
+    fn emits_every_step_of_a_deal(#[values(3, 4, 5)] n_players: u8) {
+        let mut game = Game::new(n_players);
+        let log = Arc::new(Mutex::new(EventLog::default()));
+        game.events.subscribe(log.clone());
+        let mut deal = game.new_deal().unwrap();
+        while let Some(action) = deal.legal_actions().first().copied() {
+            deal.apply(action).unwrap();
+        }
+        deal.compute_score().unwrap();
+
+        let log = log.lock().unwrap();
+        let n_cards = 78 - deal.kitty.max_size;
+        assert!(matches!(
+            log.events.first(),
+            Some(Event::DealStarted { .. })
+        ));
+        assert_eq!(
+            count(&log, |e| matches!(e, Event::BidMade { .. })),
+            n_players as usize
+        );
+        assert_eq!(
+            count(&log, |e| matches!(e, Event::CardPlayed { .. })),
+            n_cards
+        );
+        assert_eq!(
+            count(&log, |e| matches!(e, Event::TrickWon { .. })),
+            n_cards / n_players as usize
+        );
+        assert!(matches!(log.events.last(), Some(Event::DealScored { .. })));
+    }
+
+    #[test]
+    fn deals_without_observer_stay_silent() {
+        let mut game = Game::default();
+        let log = Arc::new(Mutex::new(EventLog::default()));
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        game.events.subscribe(log.clone());
+        while let Some(action) = deal.legal_actions().first().copied() {
+            deal.apply(action).unwrap();
+        }
+        assert!(log.lock().unwrap().events.is_empty());
+    }
+}
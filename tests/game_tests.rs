@@ -63,7 +63,7 @@ mod game {
     fn split_deck_conserves_original_size_of_deck() {
         let mut game = Game::default();
         let original_len = game.deck.len();
-        game.split_deck();
+        game.split_deck().unwrap();
         assert_eq!(original_len, game.deck.len());
     }
 
@@ -72,7 +72,7 @@ mod game {
         let mut game = Game::default();
         let fisrt_card = game.deck.first().cloned().unwrap();
         let last_card = game.deck.last().cloned().unwrap();
-        game.split_deck();
+        game.split_deck().unwrap();
         assert_ne!(fisrt_card.id(), game.deck.first().unwrap().id());
         assert_ne!(last_card.id(), game.deck.last().unwrap().id());
     }
@@ -92,7 +92,7 @@ mod game {
     #[test]
     fn set_only_one_dealer() {
         let mut game = Game::default();
-        game.update_dealer();
+        game.update_dealer().unwrap();
         let n_dealer = game.players.iter().filter(|p| p.is_dealer).count();
         assert_eq!(n_dealer, 1);
     }
@@ -100,10 +100,17 @@ mod game {
     #[test]
     fn update_dealer_is_next_player() {
         let mut game = Game::default();
-        let current_dealer = find_dealer(&game.players);
+        let current_dealer = find_dealer(&game.players).unwrap();
         let next_dealer = get_next_index(&game.players, current_dealer);
         assert!(game.players[current_dealer].is_dealer);
-        game.update_dealer();
+        game.update_dealer().unwrap();
         assert!(game.players[next_dealer].is_dealer);
     }
+
+    #[test]
+    fn table_without_dealer_is_an_error() {
+        let mut game = Game::new(0);
+        assert!(game.update_dealer().is_err());
+        assert!(game.reorder_players().is_err());
+    }
 }
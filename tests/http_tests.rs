@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod http {
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    use rstest::rstest;
+    use serde_json::{json, Value};
+    use tarot_cli::{
+        common::{action::Action, bid::Bids, view::SeatView},
+        net::http::{open, Created, GameInfo, Scores, SharedApi},
+    };
+
+    fn start() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || open(listener, SharedApi::default()));
+        address
+    }
+
+    fn request(
+        address: &str,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        body: &str,
+    ) -> (u16, String) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        let authorization = token
+            .map(|token| format!("Authorization: Bearer {}\r\n", token))
+            .unwrap_or_default();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: {}\r\n{}Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            address,
+            authorization,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, body.to_string())
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(address: &str, path: &str, token: Option<&str>) -> T {
+        let (status, body) = request(address, "GET", path, token, "");
+        assert_eq!(status, 200, "{}", body);
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[rstest]
+    #[case("POST", "/games", "{\"players\":6}", 400)]
+    #[case("POST", "/games", "{\"players\":4,\"seats\":5}", 400)]
+    #[case("POST", "/games", "not json", 400)]
+    #[case("GET", "/games/7", "", 404)]
+    #[case("GET", "/games/7/scores", "", 404)]
+    #[case("GET", "/cards", "", 404)]
+    #[case("OPTIONS", "/games", "", 204)]
+    fn refuses_bad_requests(
+        #[case] method: &str,
+        #[case] path: &str,
+        #[case] body: &str,
+        #[case] status: u16,
+    ) {
+        let address = start();
+        assert_eq!(request(&address, method, path, None, body).0, status);
+    }
+
+    #[test]
+    fn plays_a_game_through_the_api() {
+        let address = start();
+        let (status, body) = request(
+            &address,
+            "POST",
+            "/games",
+            None,
+            &json!({"players": 3, "seats": 2, "deals": 1}).to_string(),
+        );
+        assert_eq!(status, 201);
+        let created: Created = serde_json::from_str(&body).unwrap();
+        let info = created.game;
+        assert_eq!(info.players.len(), 3);
+        assert!(!info.over);
+        assert_eq!(
+            created
+                .tokens
+                .iter()
+                .map(|seat| seat.player)
+                .collect::<Vec<_>>(),
+            [1, 2]
+        );
+        let token = |player: u8| Some(created.tokens[player as usize - 1].token.as_str());
+        let waiting = info.waiting.unwrap();
+        assert!(waiting <= 2);
+        let other = 3 - waiting;
+
+        // Each seat only opens with its own token, and the bot has none
+        let view = format!("/games/1/players/{}", waiting);
+        assert_eq!(request(&address, "GET", &view, None, "").0, 401);
+        assert_eq!(request(&address, "GET", &view, token(other), "").0, 403);
+        assert_eq!(
+            request(&address, "GET", "/games/1/players/3", token(1), "").0,
+            403
+        );
+
+        // Nobody bids out of turn
+        let garde = serde_json::to_string(&Action::Bid(Bids::Garde)).unwrap();
+        let path = format!("/games/1/players/{}/actions", other);
+        assert_eq!(
+            request(&address, "POST", &path, token(other), &garde).0,
+            409
+        );
+        let path = format!("/games/1/players/{}/actions", waiting);
+        assert_eq!(request(&address, "POST", &path, None, &garde).0, 401);
+        let discard = "{\"Discard\":\"T21\"}";
+        let (status, body) = request(&address, "POST", &path, token(waiting), discard);
+        assert_eq!(status, 422);
+        assert!(body.contains("error"));
+
+        let mut actions = 0;
+        loop {
+            let info: GameInfo = get(&address, "/games/1", None);
+            let Some(player) = info.waiting else {
+                assert!(info.over);
+                break;
+            };
+            let path = format!("/games/1/players/{}", player);
+            let view: SeatView = get(&address, &path, token(player));
+            assert!(view.is_turn());
+            let action = serde_json::to_string(&view.legal_actions[0]).unwrap();
+            let path = format!("/games/1/players/{}/actions", player);
+            let (status, body) = request(&address, "POST", &path, token(player), &action);
+            assert_eq!(status, 200, "{}", body);
+            actions += 1;
+        }
+        assert!(actions > 0);
+
+        let history: Value = get(&address, "/games/1/history", None);
+        let events = history["events"].as_array().unwrap();
+        assert!(events.iter().any(|event| event.get("DealScored").is_some()));
+        let scores: Scores = get(&address, "/games/1/scores", None);
+        assert!(!scores.deals.is_empty());
+        assert_eq!(
+            scores
+                .totals
+                .iter()
+                .map(|total| total.id)
+                .collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        let sum: f64 = scores.totals.iter().map(|total| total.points).sum();
+        assert!(sum.abs() < 1e-6);
+    }
+}
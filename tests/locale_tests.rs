@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod locale {
+    use rstest::rstest;
+    use tarot_cli::{
+        cli::options::Options,
+        common::{
+            bid::Bids,
+            locale::{Languages, Messages},
+        },
+    };
+
+    #[rstest]
+    #[case("fr_FR.UTF-8", Some(Languages::French))]
+    #[case("fr", Some(Languages::French))]
+    #[case("fr_BE@euro", Some(Languages::French))]
+    #[case("en_GB.UTF-8", Some(Languages::English))]
+    #[case("C", None)]
+    #[case("POSIX", None)]
+    #[case("de_DE.UTF-8", None)]
+    fn reads_the_language_of_a_locale(#[case] locale: &str, #[case] expected: Option<Languages>) {
+        assert_eq!(Languages::from_locale(locale), expected);
+    }
+
+    #[rstest]
+    #[case(Messages::King, "King", "Roi")]
+    #[case(Messages::Queen, "Queen", "Dame")]
+    #[case(Messages::Knight, "Knight", "Cavalier")]
+    #[case(Messages::Jack, "Jack", "Valet")]
+    #[case(Messages::Fool, "Fool", "Excuse")]
+    #[case(Messages::GardeSans, "Garde Sans", "Garde Sans")]
+    #[case(Messages::GardeContre, "Garde Contre", "Garde Contre")]
+    #[case(Messages::SimpleHandle, "Simple handle", "poignée simple")]
+    fn translates_the_game_words(
+        #[case] message: Messages,
+        #[case] english: &str,
+        #[case] french: &str,
+    ) {
+        assert_eq!(Languages::English.text(message), english);
+        assert_eq!(Languages::French.text(message), french);
+    }
+
+    #[test]
+    fn fills_the_arguments_in_order() {
+        assert_eq!(
+            Languages::French.fill(Messages::BidMade, &[&"Alice", &"Garde"]),
+            "Alice annonce : Garde"
+        );
+        assert_eq!(
+            Languages::English.fill(Messages::PetitAuBout, &[&"attack"]),
+            "Petit au bout for the attack"
+        );
+        // Missing arguments leave the rest of the message untouched
+        assert_eq!(
+            Languages::English.fill(Messages::TrickWon, &[&"Bob"]),
+            "Bob wins the trick: "
+        );
+    }
+
+    #[test]
+    fn keeps_the_bid_ids_in_every_language() {
+        for bid in [Bids::GardeSans, Bids::GardeContre, Bids::Passe] {
+            assert_eq!(bid.id().parse::<Bids>(), Ok(bid));
+        }
+        assert_eq!(Bids::GardeSans.to_string(), "Garde Sans");
+    }
+
+    #[rstest]
+    #[case("fr", Languages::French)]
+    #[case("English", Languages::English)]
+    fn parses_the_language_flag(#[case] value: &str, #[case] expected: Languages) {
+        let options = Options::parse(["--lang", value].map(String::from)).unwrap();
+        assert_eq!(options.language, Some(expected));
+        assert!(Options::parse(["--lang", "klingon"].map(String::from)).is_err());
+    }
+}
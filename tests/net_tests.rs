@@ -0,0 +1,616 @@
+#[cfg(test)]
+mod net {
+    use std::io::BufReader;
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::{mpsc::channel, Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use rstest::rstest;
+    use tarot_cli::{
+        common::{
+            action::Action,
+            card::{Card, CardGetters, CardSuits},
+            event::{Event, Observer},
+            game::{create_deck, Game},
+            handle::Handles,
+            locale::{Languages, Messages},
+        },
+        net::{
+            client::parse_command,
+            lobby::open,
+            protocol::{receive, send, ClientMessage, Reveals, ServerMessage, TableStates},
+            server::host,
+            spectator::Spectators,
+        },
+    };
+
+    // A client always answering with the first legal action, after one illegal try
+    fn play(address: String, name: &str) -> Vec<ServerMessage> {
+        let stream = TcpStream::connect(address).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        let hello = ClientMessage::Hello {
+            name: name.to_string(),
+        };
+        send(&mut writer, &hello).unwrap();
+        let mut messages = Vec::new();
+        let mut tried = false;
+        while let Some(message) = receive::<_, ServerMessage>(&mut reader).unwrap() {
+            if let ServerMessage::Prompt { view } = &message {
+                let action = match tried {
+                    false => Action::Declare(Some(Handles::Triple)),
+                    true => view.legal_actions[0],
+                };
+                tried = true;
+                send(&mut writer, &ClientMessage::Action { action }).unwrap();
+            }
+            let over = matches!(message, ServerMessage::Goodbye { .. });
+            messages.push(message);
+            if over {
+                break;
+            }
+        }
+        messages
+    }
+
+    fn start(remote: usize, grace: Duration) -> (String, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            host(&listener, Game::new(4), remote, 1, grace).unwrap();
+        });
+        (address, server)
+    }
+
+    struct Guest {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+        token: String,
+    }
+    impl Guest {
+        fn connect(address: &str, hello: ClientMessage) -> Self {
+            let stream = TcpStream::connect(address).unwrap();
+            let mut guest = Guest {
+                reader: BufReader::new(stream.try_clone().unwrap()),
+                writer: stream,
+                token: String::new(),
+            };
+            guest.send(hello);
+            guest
+        }
+
+        fn new(address: &str, name: &str) -> Self {
+            let hello = ClientMessage::Hello {
+                name: name.to_string(),
+            };
+            let mut guest = Guest::connect(address, hello);
+            let ServerMessage::Welcome { token, .. } = guest.next() else {
+                panic!("Expected a welcome");
+            };
+            guest.token = token;
+            assert!(matches!(guest.next(), ServerMessage::Tables { .. }));
+            guest
+        }
+
+        // Reads until the first prompt, left unanswered
+        fn prompted(&mut self) {
+            while !matches!(self.next(), ServerMessage::Prompt { .. }) {}
+        }
+
+        fn send(&mut self, message: ClientMessage) {
+            send(&mut self.writer, &message).unwrap();
+        }
+
+        fn next(&mut self) -> ServerMessage {
+            receive(&mut self.reader).unwrap().unwrap()
+        }
+
+        // Plays the first legal actions until the session of the table is over
+        fn play(&mut self, table: usize) -> Vec<ServerMessage> {
+            let over = Languages::English.fill(Messages::SessionOver, &[&table]);
+            let mut messages = Vec::new();
+            loop {
+                let message = self.next();
+                if let ServerMessage::Prompt { view } = &message {
+                    let action = view.legal_actions[0];
+                    self.send(ClientMessage::Action { action });
+                }
+                if message == (ServerMessage::Notice { text: over.clone() }) {
+                    return messages;
+                }
+                messages.push(message);
+            }
+        }
+    }
+
+    fn lobby() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || open(listener, Duration::from_secs(10)));
+        address
+    }
+
+    fn replayed(messages: &[ServerMessage]) -> bool {
+        let started = messages.iter().position(|m| {
+            matches!(
+                m,
+                ServerMessage::Event {
+                    event: Event::DealStarted { .. }
+                }
+            )
+        });
+        let view = messages
+            .iter()
+            .position(|m| matches!(m, ServerMessage::View { .. }));
+        matches!((started, view), (Some(started), Some(view)) if started < view)
+    }
+
+    #[test]
+    fn writes_one_json_object_per_line() {
+        let action = ClientMessage::Action {
+            action: Action::Play(Card::new(14, CardSuits::Hearts)),
+        };
+        let mut line = Vec::new();
+        send(&mut line, &action).unwrap();
+        assert_eq!(
+            String::from_utf8(line.clone()).unwrap(),
+            "{\"type\":\"action\",\"action\":{\"Play\":\"H14\"}}\n"
+        );
+        let parsed: Option<ClientMessage> = receive(&mut line.as_slice()).unwrap();
+        assert_eq!(parsed, Some(action));
+        assert!(receive::<_, ClientMessage>(&mut "{\"type\":\"dance\"}\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn plays_a_deal_with_remote_players_and_bots() {
+        let (address, server) = start(2, Duration::from_secs(10));
+        let alice = {
+            let address = address.clone();
+            thread::spawn(move || play(address, "Alice"))
+        };
+        let bob = play(address, "Bob");
+        let alice = alice.join().unwrap();
+        server.join().unwrap();
+
+        for messages in [&alice, &bob] {
+            assert!(matches!(messages[0], ServerMessage::Welcome { .. }));
+            assert!(matches!(
+                messages.last(),
+                Some(ServerMessage::Goodbye { .. })
+            ));
+            assert!(messages
+                .iter()
+                .any(|m| matches!(m, ServerMessage::View { .. })));
+            assert!(messages.iter().any(|m| matches!(
+                m,
+                ServerMessage::Event {
+                    event: Event::DealScored { .. }
+                }
+            )));
+            // The illegal action is rejected and asked again
+            let rejected = messages
+                .iter()
+                .position(|m| matches!(m, ServerMessage::Rejected { .. }))
+                .unwrap();
+            assert!(matches!(
+                messages[rejected + 1],
+                ServerMessage::Prompt { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn a_bot_takes_the_seat_of_a_client_who_leaves() {
+        let (address, server) = start(1, Duration::from_millis(100));
+        let stream = TcpStream::connect(address).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        let hello = ClientMessage::Hello {
+            name: String::from("Carol"),
+        };
+        send(&mut writer, &hello).unwrap();
+        let welcome: Option<ServerMessage> = receive(&mut reader).unwrap();
+        assert!(matches!(
+            welcome,
+            Some(ServerMessage::Welcome { id: 1, .. })
+        ));
+        drop(reader);
+        drop(writer);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn a_client_takes_its_seat_back_with_its_token() {
+        let (address, server) = start(1, Duration::from_secs(10));
+        let hello = ClientMessage::Hello {
+            name: String::from("Carol"),
+        };
+        let mut carol = Guest::connect(&address, hello);
+        let ServerMessage::Welcome { token, .. } = carol.next() else {
+            panic!("Expected a welcome");
+        };
+        carol.prompted();
+        drop(carol);
+
+        let strangers = [
+            ClientMessage::Hello {
+                name: String::from("Mallory"),
+            },
+            ClientMessage::Resume {
+                token: String::from("0000"),
+            },
+        ];
+        for hello in strangers {
+            let mut stranger = Guest::connect(&address, hello);
+            assert!(matches!(stranger.next(), ServerMessage::Goodbye { .. }));
+        }
+
+        let mut carol = Guest::connect(
+            &address,
+            ClientMessage::Resume {
+                token: token.clone(),
+            },
+        );
+        assert_eq!(
+            carol.next(),
+            ServerMessage::Welcome {
+                id: 1,
+                name: String::from("Carol"),
+                token,
+            }
+        );
+        let mut messages = Vec::new();
+        loop {
+            let message = carol.next();
+            if let ServerMessage::Prompt { view } = &message {
+                let action = view.legal_actions[0];
+                carol.send(ClientMessage::Action { action });
+            }
+            if matches!(message, ServerMessage::Goodbye { .. }) {
+                break;
+            }
+            messages.push(message);
+        }
+        server.join().unwrap();
+        assert!(replayed(&messages));
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            ServerMessage::Event {
+                event: Event::DealScored { .. }
+            }
+        )));
+    }
+
+    #[rstest]
+    #[case("/tables", ClientMessage::List)]
+    #[case("/create 4 2", ClientMessage::Create { players: 4, seats: 2, deals: 1, reveal: Reveals::Never })]
+    #[case("/create 5 1 3", ClientMessage::Create { players: 5, seats: 1, deals: 3, reveal: Reveals::Never })]
+    #[case("/create 4 1 2 end", ClientMessage::Create { players: 4, seats: 1, deals: 2, reveal: Reveals::AtEnd })]
+    #[case("/create 3 0 1 3", ClientMessage::Create { players: 3, seats: 0, deals: 1, reveal: Reveals::AfterTricks(3) })]
+    #[case("/sit 2", ClientMessage::Sit { table: 2 })]
+    #[case("/leave", ClientMessage::Leave)]
+    #[case("/restart", ClientMessage::Restart)]
+    #[case("/say  on y va ?", ClientMessage::Say { text: String::from("on y va ?") })]
+    #[case("/watch 1", ClientMessage::Watch { table: 1 })]
+    fn reads_the_lobby_commands(#[case] line: &str, #[case] expected: ClientMessage) {
+        assert_eq!(parse_command(line), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("/create 4")]
+    #[case("/sit first")]
+    #[case("/dance")]
+    #[case("/create 4 1 1 later")]
+    fn refuses_unknown_commands(#[case] line: &str) {
+        assert!(parse_command(line).is_err());
+    }
+
+    #[test]
+    fn hosts_tables_with_their_own_chat() {
+        let address = lobby();
+        let mut alice = Guest::new(&address, "Alice");
+        alice.send(ClientMessage::Create {
+            players: 4,
+            seats: 2,
+            deals: 1,
+            reveal: Reveals::Never,
+        });
+        let ServerMessage::Tables { tables } = alice.next() else {
+            panic!("Expected the tables");
+        };
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].state, TableStates::Waiting);
+
+        alice.send(ClientMessage::Say {
+            text: String::from("Hello"),
+        });
+        assert!(matches!(alice.next(), ServerMessage::Rejected { .. }));
+        alice.send(ClientMessage::Sit { table: 1 });
+        assert!(matches!(alice.next(), ServerMessage::Notice { .. }));
+        alice.send(ClientMessage::Say {
+            text: String::from("Hello"),
+        });
+        assert_eq!(
+            alice.next(),
+            ServerMessage::Chat {
+                name: String::from("Alice"),
+                text: String::from("Hello"),
+            }
+        );
+
+        // Carol is alone at another table and never hears the first one
+        let mut carol = Guest::new(&address, "Carol");
+        carol.send(ClientMessage::Sit { table: 9 });
+        assert!(matches!(carol.next(), ServerMessage::Rejected { .. }));
+        carol.send(ClientMessage::Create {
+            players: 3,
+            seats: 1,
+            deals: 1,
+            reveal: Reveals::Never,
+        });
+        assert!(matches!(carol.next(), ServerMessage::Tables { .. }));
+        carol.send(ClientMessage::Sit { table: 2 });
+        assert!(matches!(carol.next(), ServerMessage::Notice { .. }));
+        assert_eq!(carol.next(), ServerMessage::Seated { table: 2, id: 1 });
+
+        let mut bob = Guest::new(&address, "Bob");
+        bob.send(ClientMessage::Sit { table: 1 });
+        let bob = thread::spawn(move || bob.play(1));
+        let alice = alice.play(1);
+        let bob = bob.join().unwrap();
+        let carol = carol.play(2);
+
+        for messages in [&alice, &bob, &carol] {
+            assert!(messages.iter().any(|m| matches!(
+                m,
+                ServerMessage::Event {
+                    event: Event::DealScored { .. }
+                }
+            )));
+            assert!(!messages
+                .iter()
+                .any(|m| matches!(m, ServerMessage::Chat { .. })));
+        }
+        assert!(alice.contains(&ServerMessage::Seated { table: 1, id: 1 }));
+        assert!(bob.contains(&ServerMessage::Seated { table: 1, id: 2 }));
+        let started = |messages: &[ServerMessage]| {
+            messages.iter().find_map(|m| match m {
+                ServerMessage::Event {
+                    event: Event::DealStarted { players },
+                } => Some(players.len()),
+                _ => None,
+            })
+        };
+        assert_eq!(started(&alice), Some(4));
+        assert_eq!(started(&carol), Some(3));
+    }
+
+    #[test]
+    fn a_client_leaving_a_session_gets_nothing_more_from_the_table() {
+        let address = lobby();
+        let mut erin = Guest::new(&address, "Erin");
+        erin.send(ClientMessage::Create {
+            players: 4,
+            seats: 1,
+            deals: 2,
+            reveal: Reveals::Never,
+        });
+        erin.next();
+        erin.send(ClientMessage::Sit { table: 1 });
+        erin.prompted();
+        erin.send(ClientMessage::Leave);
+        assert!(matches!(erin.next(), ServerMessage::Notice { .. }));
+        erin.send(ClientMessage::List);
+        assert!(matches!(erin.next(), ServerMessage::Tables { .. }));
+
+        // The bots play the rest of the session without writing to Erin
+        erin.reader
+            .get_ref()
+            .set_read_timeout(Some(Duration::from_millis(500)))
+            .unwrap();
+        assert!(receive::<_, ServerMessage>(&mut erin.reader).is_err());
+    }
+
+    #[test]
+    fn restarts_a_session_over() {
+        let address = lobby();
+        let mut dave = Guest::new(&address, "Dave");
+        dave.send(ClientMessage::Restart);
+        assert!(matches!(dave.next(), ServerMessage::Rejected { .. }));
+        dave.send(ClientMessage::Create {
+            players: 3,
+            seats: 1,
+            deals: 1,
+            reveal: Reveals::Never,
+        });
+        dave.next();
+        dave.send(ClientMessage::Sit { table: 1 });
+        dave.play(1);
+        dave.send(ClientMessage::Restart);
+        let again = dave.play(1);
+        assert!(again.contains(&ServerMessage::Seated { table: 1, id: 1 }));
+
+        dave.send(ClientMessage::Leave);
+        assert!(matches!(dave.next(), ServerMessage::Notice { .. }));
+        dave.send(ClientMessage::List);
+        assert_eq!(dave.next(), ServerMessage::Tables { tables: vec![] });
+    }
+
+    // Reads the messages of a spectator until the deal is scored
+    fn follow(guest: &mut Guest) -> Vec<ServerMessage> {
+        let mut messages = Vec::new();
+        loop {
+            let message = guest.next();
+            let scored = matches!(
+                message,
+                ServerMessage::Event {
+                    event: Event::DealScored { .. }
+                }
+            );
+            messages.push(message);
+            if scored {
+                return messages;
+            }
+        }
+    }
+
+    #[test]
+    fn spectators_of_remote_players_see_the_hands_at_the_end() {
+        let address = lobby();
+        let mut alice = Guest::new(&address, "Alice");
+        alice.send(ClientMessage::Create {
+            players: 4,
+            seats: 1,
+            deals: 1,
+            reveal: Reveals::AfterTricks(2),
+        });
+        assert!(matches!(alice.next(), ServerMessage::Rejected { .. }));
+        alice.send(ClientMessage::Create {
+            players: 4,
+            seats: 1,
+            deals: 1,
+            reveal: Reveals::AtEnd,
+        });
+        alice.next();
+
+        let mut eve = Guest::new(&address, "Eve");
+        eve.send(ClientMessage::Watch { table: 1 });
+        assert!(matches!(eve.next(), ServerMessage::Notice { .. }));
+        eve.send(ClientMessage::Say {
+            text: String::from("Play the king!"),
+        });
+        assert!(matches!(eve.next(), ServerMessage::Rejected { .. }));
+        eve.send(ClientMessage::Sit { table: 1 });
+        assert!(matches!(eve.next(), ServerMessage::Rejected { .. }));
+
+        alice.send(ClientMessage::Sit { table: 1 });
+        let alice = alice.play(1);
+        let messages = follow(&mut eve);
+        assert!(!messages.iter().any(|m| matches!(
+            m,
+            ServerMessage::Prompt { .. }
+                | ServerMessage::View { .. }
+                | ServerMessage::Chat { .. }
+                | ServerMessage::Hands { .. }
+        )));
+
+        let ServerMessage::Hands { hands, .. } = eve.next() else {
+            panic!("Expected the hands at the end of the deal");
+        };
+        let Some(ServerMessage::View { view }) = alice
+            .iter()
+            .rev()
+            .find(|m| matches!(m, ServerMessage::View { .. }))
+        else {
+            panic!("Expected the view of Alice");
+        };
+        let mut hand = hands[view.seat].clone();
+        let mut dealt = view.hand.clone();
+        hand.sort_by_key(|card| card.id());
+        dealt.sort_by_key(|card| card.id());
+        assert_eq!(hand, dealt);
+    }
+
+    #[test]
+    fn a_table_of_bots_plays_for_its_spectators() {
+        let address = lobby();
+        let mut frank = Guest::new(&address, "Frank");
+        frank.send(ClientMessage::Create {
+            players: 3,
+            seats: 0,
+            deals: 1,
+            reveal: Reveals::AfterTricks(2),
+        });
+        frank.next();
+        frank.send(ClientMessage::Watch { table: 1 });
+        assert!(matches!(frank.next(), ServerMessage::Notice { .. }));
+        let messages = follow(&mut frank);
+
+        let revealed = messages
+            .iter()
+            .position(|m| matches!(m, ServerMessage::Hands { .. }))
+            .unwrap();
+        let tricks = messages[..revealed]
+            .iter()
+            .filter(|m| {
+                matches!(
+                    m,
+                    ServerMessage::Event {
+                        event: Event::TrickWon { .. }
+                    }
+                )
+            })
+            .count();
+        assert_eq!(tricks, 2);
+    }
+
+    #[test]
+    fn a_lobby_client_takes_its_seat_back_with_its_token() {
+        let address = lobby();
+        let mut grace = Guest::new(&address, "Grace");
+        grace.send(ClientMessage::Create {
+            players: 3,
+            seats: 1,
+            deals: 1,
+            reveal: Reveals::Never,
+        });
+        grace.next();
+        grace.send(ClientMessage::Sit { table: 1 });
+        grace.prompted();
+        let token = grace.token.clone();
+        drop(grace);
+
+        let mut stranger = Guest::new(&address, "Mallory");
+        stranger.send(ClientMessage::Resume {
+            token: String::from("0000"),
+        });
+        assert!(matches!(stranger.next(), ServerMessage::Rejected { .. }));
+
+        let mut grace = Guest::connect(
+            &address,
+            ClientMessage::Resume {
+                token: token.clone(),
+            },
+        );
+        assert_eq!(
+            grace.next(),
+            ServerMessage::Welcome {
+                id: 0,
+                name: String::from("Grace"),
+                token,
+            }
+        );
+        assert_eq!(grace.next(), ServerMessage::Seated { table: 1, id: 1 });
+        let messages = grace.play(1);
+        assert!(replayed(&messages));
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            ServerMessage::Event {
+                event: Event::DealScored { .. }
+            }
+        )));
+    }
+
+    #[test]
+    fn a_spectator_who_stops_reading_is_dropped() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let _reader = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (writer, _) = listener.accept().unwrap();
+        let (dropped, reported) = channel();
+        let mut spectators = Spectators::new(Reveals::Never).on_drop(move |client| {
+            let _ = dropped.send(client);
+        });
+        spectators.watch(1, Arc::new(Mutex::new(writer)));
+
+        // Far more than the socket buffers, the table goes on without waiting
+        let kitty = Event::KittyRevealed {
+            cards: create_deck(),
+        };
+        for _ in 0..20_000 {
+            spectators.notify(&kitty);
+        }
+        assert!(spectators.is_empty());
+        assert_eq!(reported.try_iter().collect::<Vec<_>>(), [1]);
+    }
+}
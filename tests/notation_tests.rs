@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod notation {
+    use rstest::rstest;
+    use tarot_cli::common::{
+        bid::Bids,
+        card::{Card, CardGetters, CardSuits},
+        game::Game,
+        notation::{DealRecord, NotationError, RecordedTrick},
+    };
+
+    fn sample_record() -> DealRecord {
+        let game = Game::default();
+        let mut record = DealRecord::new(
+            game.players
+                .iter()
+                .map(|player| player.name.clone())
+                .collect(),
+        );
+        record.players[2] = String::from("Smith, J. \\o/");
+        record.seed = Some(42);
+        record.dealer = Some(3);
+        for (index, card) in game.deck[..72].iter().enumerate() {
+            record.hands[index % 4].push(*card);
+        }
+        record.kitty = game.deck[72..].to_vec();
+        record.auction = Vec::from([
+            (0, Bids::Passe),
+            (1, Bids::Garde),
+            (2, Bids::Passe),
+            (3, Bids::Passe),
+        ]);
+        record.ecart = record.hands[1][..6].to_vec();
+        record.tricks.push(RecordedTrick {
+            leader: 0,
+            cards: (0..4).map(|seat| record.hands[seat][17]).collect(),
+        });
+        record
+    }
+
+    #[test]
+    fn card_ids_round_trip() {
+        let game = Game::default();
+        for card in game.deck {
+            assert_eq!(card.id().parse::<Card>(), Ok(card));
+        }
+    }
+
+    #[rstest]
+    fn rejects_invalid_card_ids(#[values("X1", "C15", "C0", "T23", "H", "")] id: &str) {
+        assert!(id.parse::<Card>().is_err());
+    }
+
+    #[test]
+    fn export_then_import_gives_the_same_record() {
+        let record = sample_record();
+        let text = record.export();
+        assert_eq!(DealRecord::import(&text), Ok(record));
+    }
+
+    #[test]
+    fn imports_a_handwritten_deal() {
+        let text = "
+            [Variant \"4\"]
+            [Players \"Alice, Bob, Carol, Dave\"]
+            ; the auction ends with a Garde from Bob
+            Auction: 0:Passe 1:Garde 2:Passe 3:Passe
+            1. 1: C14 C2 T5 C7
+        ";
+        let record = DealRecord::import(text).unwrap();
+        assert_eq!(record.players[1], "Bob");
+        assert_eq!(record.auction[1], (1, Bids::Garde));
+        assert_eq!(record.tricks[0].leader, 1);
+        assert_eq!(record.tricks[0].cards[2], Card::new(5, CardSuits::Trumps));
+    }
+
+    #[rstest]
+    fn rejects_malformed_deals(
+        #[values(
+            ("Auction: 0:Passe", NotationError::MissingTag("Players")),
+            ("[Players \"A, B, C, D\"]\nAuction: 0:Pass", NotationError::InvalidBid(String::from("Unknown bid 'Pass'"))),
+            ("[Players \"A, B, C, D\"]\nAuction: 7:Passe", NotationError::InvalidSeat(String::from("7"))),
+            ("[Players \"A, B, C, D\"]\n[Hand0 \"C1\"]\n[Kitty \"C1\"]", NotationError::DuplicatedCard(String::from("C1"))),
+            ("[Players \"A, B, C, D\"]\nHello", NotationError::MalformedLine(String::from("Hello"))),
+            ("[Players \"A, B, C, D\"]\n[Dealer \"4\"]", NotationError::InvalidSeat(String::from("4"))),
+            ("[Players \"A, B\"]", NotationError::InvalidPlayers(2)),
+            ("[Players \"A\\, B, C\"]", NotationError::InvalidPlayers(2)),
+        )]
+        case: (&str, NotationError),
+    ) {
+        let (text, expected_error) = case;
+        assert_eq!(DealRecord::import(text), Err(expected_error));
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod profile {
+    use std::env;
+    use std::fs;
+
+    use tarot_cli::{
+        cli::options::{Commands, Options},
+        common::{
+            deal::{Deal, DealActions},
+            game::{Game, GameActions},
+            hand::Side,
+            phase::Phase,
+            player::PlayerActions,
+            profile::{Profile, Profiles},
+        },
+    };
+
+    // Bots play until a deal is taken and scored
+    fn scored_deal() -> Deal {
+        let mut game = Game::new(4);
+        for player in game.players.iter_mut() {
+            player.is_human = false;
+        }
+        loop {
+            game.reorder_players().unwrap();
+            let mut deal = game.new_deal().unwrap();
+            while !matches!(deal.phase, Phase::Scoring | Phase::Done) {
+                let action = deal.current_player().act(&deal.view(deal.current)).unwrap();
+                deal.apply(action).unwrap();
+            }
+            if deal.phase == Phase::Scoring {
+                deal.compute_score().unwrap();
+                return deal;
+            }
+            game.collect_deck(&deal).unwrap();
+        }
+    }
+
+    #[test]
+    fn records_the_deals_of_the_human_players() {
+        let mut deal = scored_deal();
+        let taker = deal.taker.clone().unwrap();
+        let defender = (0..4)
+            .find(|seat| deal.side(*seat) == Side::Defense)
+            .unwrap();
+        deal.players[taker.seat].is_human = true;
+        deal.players[defender].is_human = true;
+        let mut profiles = Profiles::default();
+        profiles.record_deal(&deal);
+        profiles.record_deal(&deal);
+        let names: Vec<String> = [taker.seat, defender]
+            .iter()
+            .map(|seat| deal.players[*seat].name.clone())
+            .collect();
+        profiles.record_game(&names);
+        assert_eq!(profiles.players.len(), 2);
+
+        let score = deal.score.clone().unwrap();
+        let attacker = &profiles.players[&names[0]];
+        assert_eq!((attacker.games, attacker.deals), (1, 2));
+        assert_eq!(attacker.taken(), 2);
+        assert_eq!(
+            attacker.contract(score.bid).won,
+            2 * score.contract_won as usize
+        );
+        assert_eq!(attacker.average_as_taker(), score.scores[taker.seat]);
+        assert_eq!(attacker.average_as_defender(), 0.0);
+
+        let defender_profile = &profiles.players[&names[1]];
+        assert_eq!(defender_profile.taken(), 0);
+        assert_eq!(defender_profile.defended, 2);
+        assert_eq!(
+            defender_profile.average_as_defender(),
+            score.scores[defender]
+        );
+        let handles = |seat| 2 * deal.handles.iter().filter(|h| h.seat == seat).count();
+        assert_eq!(attacker.handles, handles(taker.seat));
+        assert_eq!(defender_profile.handles, handles(defender));
+    }
+
+    #[test]
+    fn keeps_the_profiles_in_a_file() {
+        let path = env::temp_dir()
+            .join(format!("tarot-profiles-{}", std::process::id()))
+            .join("profiles.json");
+        assert_eq!(Profiles::load(&path).unwrap(), Profiles::default());
+
+        let mut profiles = Profiles::default();
+        profiles.record_game(&[String::from("Alice"), String::from("Bob")]);
+        profiles.players.get_mut("Bob").unwrap().points = 120.0;
+        profiles.players.get_mut("Alice").unwrap().points = -40.0;
+        profiles.save(&path).unwrap();
+        let loaded = Profiles::load(&path).unwrap();
+        assert_eq!(loaded, profiles);
+        let leaders: Vec<&String> = loaded.leaderboard().into_iter().map(|(n, _)| n).collect();
+        assert_eq!(leaders, ["Bob", "Alice"]);
+        assert!(loaded.to_string().contains("Bob"));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn averages_nothing_without_deals() {
+        let profile = Profile::default();
+        assert_eq!(profile.average_as_taker(), 0.0);
+        assert_eq!(profile.average_as_defender(), 0.0);
+    }
+
+    #[test]
+    fn reads_the_stats_command() {
+        let args = "stats --profiles season.json --name Alice";
+        let options = Options::parse(args.split(' ').map(String::from)).unwrap();
+        assert_eq!(options.command, Commands::Stats);
+        assert_eq!(options.profiles().to_str(), Some("season.json"));
+        assert_eq!(options.name.as_deref(), Some("Alice"));
+    }
+}
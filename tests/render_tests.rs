@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod render {
+    use rstest::rstest;
+    use tarot_cli::{
+        cli::options::Options,
+        common::{
+            card::{Card, CardSuits},
+            render::{numbered, paint, render_hand},
+        },
+    };
+
+    fn hand() -> Vec<Card> {
+        vec![
+            Card::new(21, CardSuits::Trumps),
+            Card::new(14, CardSuits::Hearts),
+            Card::new(3, CardSuits::Clubs),
+            Card::new(5, CardSuits::Trumps),
+            Card::new(11, CardSuits::Clubs),
+            Card::new(2, CardSuits::Hearts),
+        ]
+    }
+
+    #[test]
+    fn groups_the_hand_by_suit_and_rank() {
+        assert_eq!(
+            render_hand(&hand(), &[], false, 80),
+            "Clubs     3♣  J♣\nHearts    2♥  K♥\nTrumps    5*  21*"
+        );
+    }
+
+    #[test]
+    fn numbers_only_the_legal_cards() {
+        let hand = hand();
+        let legal = [hand[0], hand[3]];
+        assert_eq!(
+            render_hand(&hand, &legal, false, 80),
+            "Clubs     3♣  J♣\nHearts    2♥  K♥\nTrumps    0:5*  1:21*"
+        );
+        assert_eq!(numbered(&legal), [hand[3], hand[0]]);
+    }
+
+    #[test]
+    fn wraps_to_the_width() {
+        let hand: Vec<Card> = (1..=10)
+            .map(|rank| Card::new(rank, CardSuits::Spades))
+            .collect();
+        let rendered = render_hand(&hand, &hand, false, 40);
+        assert_eq!(
+            rendered,
+            "Spades    0:1♠  1:2♠  2:3♠  3:4♠  4:5♠\n          5:6♠  6:7♠  7:8♠  8:9♠  9:10♠"
+        );
+    }
+
+    #[rstest]
+    #[case(Card::new(14, CardSuits::Hearts), "\u{1b}[38;5;9m")]
+    #[case(Card::new(1, CardSuits::Trumps), "\u{1b}[1m")]
+    #[case(Card::new(8, CardSuits::Trumps), "\u{1b}[38;5;12m")]
+    fn colors_the_cards(#[case] card: Card, #[case] code: &str) {
+        assert!(
+            paint(&card, true).contains(code),
+            "{:?}",
+            paint(&card, true)
+        );
+        assert!(!paint(&card, false).contains('\u{1b}'));
+    }
+
+    #[test]
+    fn red_and_black_suits_differ() {
+        let black = paint(&Card::new(14, CardSuits::Spades), true);
+        let red = paint(&Card::new(14, CardSuits::Diamonds), true);
+        assert!(!black.contains("38;5;9m"));
+        assert!(red.contains("38;5;9m"));
+    }
+
+    #[test]
+    fn parses_no_color() {
+        let options = Options::parse(["--no-color"].map(String::from)).unwrap();
+        assert!(options.no_color);
+    }
+}
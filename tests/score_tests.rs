@@ -1,9 +1,11 @@
 #[cfg(test)]
 mod score {
     use tarot_cli::common::{
+        bid::Bids,
         card::{Card, CardSuitsGetters},
         game::Game,
-        score::{compute_oudlers, compute_points},
+        hand::Side,
+        score::{compute_deal_score, compute_oudlers, compute_points, ScoreInput},
     };
 
     #[test]
@@ -52,4 +54,42 @@ mod score {
             assert_eq!(compute_points(&suit_cards), 17.0);
         }
     }
+
+    #[test]
+    fn taker_with_all_cards_wins_from_each_defender() {
+        let game = Game::default();
+        let score = compute_deal_score(&ScoreInput {
+            bid: Bids::Garde,
+            attack_cards: &game.deck,
+            sides: &[Side::Defense, Side::Attack, Side::Defense, Side::Defense],
+            taker: 1,
+            petit_au_bout: None,
+            handles: &[],
+            slam: None,
+        });
+        assert!(score.contract_won);
+        assert_eq!(score.scores, Vec::from([-160.0, 480.0, -160.0, -160.0]));
+    }
+
+    #[test]
+    fn partner_and_taker_share_the_gain_at_five_players() {
+        let game = Game::default();
+        let score = compute_deal_score(&ScoreInput {
+            bid: Bids::Petite,
+            attack_cards: &game.deck[..0],
+            sides: &[
+                Side::Attack,
+                Side::Defense,
+                Side::Attack,
+                Side::Defense,
+                Side::Defense,
+            ],
+            taker: 2,
+            petit_au_bout: Some(Side::Defense),
+            handles: &[],
+            slam: None,
+        });
+        assert!(!score.contract_won);
+        assert_eq!(score.scores, Vec::from([-91.0, 91.0, -182.0, 91.0, 91.0]));
+    }
 }
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod simulation {
+    use rstest::rstest;
+    use tarot_cli::{
+        bots::difficulty::Difficulties,
+        cli::options::{Commands, Options},
+        common::{
+            bid::Bids,
+            simulation::{play_deal, simulate, Outcome, SimulationConfig, Statistics},
+        },
+    };
+
+    fn config(deals: usize, threads: usize) -> SimulationConfig {
+        SimulationConfig {
+            deals,
+            threads,
+            seed: Some(42),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    fn plays_every_deal_to_the_end(#[values(3, 4, 5)] n_players: u8) {
+        let statistics = simulate(&SimulationConfig {
+            n_players,
+            levels: vec![Difficulties::Beginner, Difficulties::Intermediate],
+            ..config(60, 2)
+        });
+        assert_eq!(statistics.failures, []);
+        assert_eq!(statistics.deals, 60);
+        assert_eq!(statistics.passed + statistics.scored(), 60);
+        assert_eq!(statistics.scores.len(), n_players as usize);
+        // Every deal is a zero-sum game
+        assert!(statistics.scores.iter().sum::<f64>().abs() < 1e-9);
+    }
+
+    #[test]
+    fn seeded_simulations_do_not_depend_on_threads() {
+        let single = simulate(&config(40, 1));
+        let parallel = simulate(&config(40, 4));
+        assert_eq!(single, parallel);
+        assert_eq!(single.seed, 42);
+    }
+
+    #[test]
+    fn replays_a_deal_from_its_seed() {
+        let config = config(1, 1);
+        assert_eq!(play_deal(&config, 7), play_deal(&config, 7));
+    }
+
+    #[test]
+    fn records_outcomes() {
+        let mut statistics = Statistics::default();
+        statistics.record(1, Outcome::Passed);
+        statistics.record(2, Outcome::Failed(String::from("panic")));
+        assert_eq!(statistics.deals, 2);
+        assert_eq!(statistics.passed, 1);
+        assert_eq!(statistics.failures, [(2, String::from("panic"))]);
+        assert_eq!(statistics.contract(Bids::Garde).taken, 0);
+        assert_eq!(statistics.average_score(0), 0.0);
+    }
+
+    #[test]
+    fn parses_the_simulate_command() {
+        let args =
+            "simulate --deals 50 --threads 3 --players 5 --seat 2=expert --seed 9 --deal-seed 3";
+        let options = Options::parse(args.split(' ').map(String::from)).unwrap();
+        assert_eq!(options.command, Commands::Simulate);
+
+        let config = options.simulation().unwrap();
+        assert_eq!((config.deals, config.threads, config.n_players), (50, 3, 5));
+        assert_eq!(config.level(1), Difficulties::Expert);
+        assert_eq!(config.level(4), Difficulties::Intermediate);
+        assert_eq!((config.seed, config.search.seed), (Some(3), Some(9)));
+    }
+
+    #[rstest]
+    #[case("simulate --seat 1=human")]
+    #[case("simulate --seat 6=expert")]
+    #[case("simulate --players 6")]
+    #[case("--deals 10 simulate")]
+    fn rejects_invalid_simulations(#[case] args: &str) {
+        let options = Options::parse(args.split(' ').map(String::from));
+        assert!(options.and_then(|options| options.simulation()).is_err());
+    }
+}
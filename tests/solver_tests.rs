@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod solver {
+    use rstest::rstest;
+    use tarot_cli::{
+        bots::solver::{solve, Solver},
+        common::{
+            action::Action, card::CardGetters, deal::Deal, game::Game, hand::Side, phase::Phase,
+            trick::allowed_cards_to_play,
+        },
+    };
+
+    use crate::common::play_until;
+
+    fn play_until_cards_left(deal: &mut Deal, cards_left: usize) {
+        play_until(deal, |deal| {
+            deal.phase >= Phase::Play && deal.current_player().hand.cards.len() <= cards_left
+        });
+    }
+
+    // Plain minimax over every line of play, to check the pruned search
+    fn minimax(deal: &Deal) -> f64 {
+        if deal.phase != Phase::Play {
+            return attack_won_points(deal);
+        }
+        let is_attack = deal.side(deal.current) == Side::Attack;
+        let cards = allowed_cards_to_play(&deal.trick, &deal.current_player().hand.cards);
+        let values = cards.into_iter().map(|card| {
+            let mut deal = deal.clone();
+            deal.apply(Action::Play(card)).unwrap();
+            minimax(&deal)
+        });
+        match is_attack {
+            true => values.fold(f64::NEG_INFINITY, f64::max),
+            false => values.fold(f64::INFINITY, f64::min),
+        }
+    }
+
+    fn attack_won_points(deal: &Deal) -> f64 {
+        deal.players
+            .iter()
+            .enumerate()
+            .filter(|(seat, _)| deal.side(*seat) == Side::Attack)
+            .flat_map(|(_, player)| player.hand.won_cards.iter())
+            .map(|card| card.score())
+            .sum()
+    }
+
+    // The half point given back for the Fool is only exchanged when scoring
+    #[rstest]
+    fn matches_a_full_minimax_in_the_endgame(#[values(3, 4, 5)] n_players: u8) {
+        let mut game = Game::new(n_players);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until_cards_left(&mut deal, 3);
+
+        let solution = solve(&deal).unwrap();
+        let expected = minimax(&deal) - attack_won_points(&deal);
+        assert!((solution.attack - expected).abs() <= 0.5);
+    }
+
+    #[rstest]
+    fn shares_all_the_remaining_points(#[values(3, 4, 5)] n_players: u8) {
+        let mut game = Game::new(n_players);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until_cards_left(&mut deal, 4);
+        let remaining: f64 = deal
+            .players
+            .iter()
+            .flat_map(|player| player.hand.cards.iter())
+            .chain(&deal.trick.played_cards)
+            .map(|card| card.score())
+            .sum();
+
+        let solution = solve(&deal).unwrap();
+        assert_eq!(solution.attack + solution.defense, remaining);
+        assert!(solution.attack >= 0.0 && solution.defense >= 0.0);
+        assert!(deal
+            .legal_actions()
+            .contains(&Action::Play(solution.best.unwrap())));
+    }
+
+    fn count_nodes(deal: &Deal) -> usize {
+        if deal.phase != Phase::Play {
+            return 1;
+        }
+        let cards = allowed_cards_to_play(&deal.trick, &deal.current_player().hand.cards);
+        cards.into_iter().fold(1, |count, card| {
+            let mut deal = deal.clone();
+            deal.apply(Action::Play(card)).unwrap();
+            count + count_nodes(&deal)
+        })
+    }
+
+    #[test]
+    fn prunes_and_caches_the_search() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until_cards_left(&mut deal, 4);
+
+        let mut solver = Solver::default();
+        solver.solve(&deal).unwrap();
+        assert!(solver.nodes > 0);
+        assert!(solver.nodes < count_nodes(&deal));
+    }
+
+    #[test]
+    fn rejects_deals_out_of_the_play() {
+        let mut game = Game::default();
+        let deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        assert!(solve(&deal).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tracker {
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+    use rstest::rstest;
+    use tarot_cli::{
+        bots::tracker::Tracker,
+        common::{
+            action::Action,
+            bid::Bids,
+            card::{Card, CardSuits, CardSuitsGetters},
+            deal::Deal,
+            game::{new_deck, Game},
+            phase::Phase,
+            trick::TrickGetters,
+        },
+    };
+
+    use crate::common::play_until;
+
+    #[rstest]
+    fn follows_the_played_cards(#[values(3, 4, 5)] n_players: u8) {
+        let mut game = Game::new(n_players);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| deal.tricks.len() >= 3);
+        let tracker = Tracker::new(&deal.view(deal.current));
+
+        let played = tracker.played_cards();
+        assert_eq!(played.len(), 3 * n_players as usize);
+        assert!(deal.tricks[0]
+            .played_cards
+            .iter()
+            .all(|card| tracker.is_played(card)));
+        let played_trumps = played
+            .iter()
+            .filter(|card| card.suit.is_trump() && card.rank != 22)
+            .count();
+        assert_eq!(tracker.remaining_trumps(), 21 - played_trumps);
+    }
+
+    #[test]
+    fn knows_its_own_cards() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| !deal.tricks.is_empty());
+        let seat = deal.current;
+        let tracker = Tracker::new(&deal.view(seat));
+
+        for card in &deal.players[seat].hand.cards {
+            assert_eq!(tracker.probability(seat, card), 1.0);
+            assert_eq!(tracker.probability((seat + 1) % 4, card), 0.0);
+            assert!(tracker.candidates(card).is_empty());
+        }
+    }
+
+    #[test]
+    fn shares_unseen_cards_between_the_other_seats() {
+        // A random deal may leave a card that only the kitty can hold, this one does not
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut game = Game::default();
+        let mut deck = new_deck();
+        deck.shuffle(&mut rng);
+        let mut deal = Deal::deal_with(&mut game.players, &mut deck, &mut rng).unwrap();
+        play_until(&mut deal, |deal| deal.tricks.len() >= 2);
+        let tracker = Tracker::new(&deal.view(deal.current));
+
+        for card in tracker.unseen() {
+            let total: f64 = (0..4).map(|seat| tracker.probability(seat, &card)).sum();
+            let in_pile = tracker.candidates(&card).contains(&tracker.pile());
+            assert!(total > 0.0 && total <= 1.0 + f64::EPSILON);
+            assert!(in_pile || (total - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn proves_voids_from_the_played_cards() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        for bid in [Bids::GardeSans, Bids::Passe, Bids::Passe, Bids::Passe] {
+            deal.apply(Action::Bid(bid)).unwrap();
+        }
+        play_until(&mut deal, |deal| deal.phase >= Phase::Play);
+        while deal.phase == Phase::Play {
+            let suit = deal.trick.played_suit();
+            let seat = deal.current;
+            let action = deal.legal_actions()[0];
+            deal.apply(action).unwrap();
+            let Action::Play(card) = action else {
+                continue;
+            };
+            if let Some(suit) = suit.filter(|suit| *suit != card.suit.name && card.rank != 22) {
+                let tracker = Tracker::new(&deal.view((seat + 1) % 4));
+                assert!(tracker.is_void(seat, suit));
+                assert_eq!(tracker.probability(seat, &Card::new(14, suit)), 0.0);
+                return;
+            }
+        }
+    }
+
+    #[test]
+    fn reveals_the_partner_with_the_called_king() {
+        let mut game = Game::new(5);
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| deal.phase >= Phase::Play);
+        let taker = deal.taker.clone().unwrap().seat;
+        let king = deal.called_king.unwrap();
+        let holder = deal
+            .players
+            .iter()
+            .position(|player| player.hand.cards.contains(&king));
+        let observer = (0..5)
+            .find(|seat| *seat != taker && Some(*seat) != holder)
+            .unwrap();
+        assert_eq!(Tracker::new(&deal.view(observer)).partner, None);
+
+        let is_played = |deal: &Deal| {
+            deal.tricks
+                .iter()
+                .chain([&deal.trick])
+                .any(|trick| trick.played_cards.contains(&king))
+        };
+        play_until(&mut deal, |deal| {
+            deal.phase != Phase::Play || is_played(deal)
+        });
+        let tracker = Tracker::new(&deal.view(observer));
+        assert_eq!(tracker.called_king_seen, holder.is_some());
+        assert_eq!(tracker.partner, holder.filter(|seat| *seat != taker));
+    }
+
+    #[test]
+    fn finds_master_cards() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| !deal.tricks.is_empty());
+        let seat = deal.current;
+        let tracker = Tracker::new(&deal.view(seat));
+        let big = Card::new(21, CardSuits::Trumps);
+        if deal.players[seat].hand.cards.contains(&big) {
+            assert!(tracker.is_master(&big));
+        }
+        assert!(!tracker.is_master(&Card::new(22, CardSuits::Trumps)));
+    }
+}
@@ -14,7 +14,9 @@ mod trick {
             (Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Some(1)),
             (Vec::from([Card::new(8, CardSuits::Clubs), Card::new(10, CardSuits::Clubs), Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Clubs), Card::new(1, CardSuits::Clubs)]), Some(2)),
             (Vec::from([Card::new(8, CardSuits::Trumps), Card::new(10, CardSuits::Trumps), Card::new(14, CardSuits::Trumps), Card::new(2, CardSuits::Trumps), Card::new(1, CardSuits::Trumps)]), Some(2)),
-            (Vec::from([Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs)]), Some(0)))]
+            (Vec::from([Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs)]), Some(0)),
+            (Vec::from([Card::new(22, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(5, CardSuits::Clubs)]), Some(2)),
+            (Vec::from([Card::new(3, CardSuits::Hearts), Card::new(22, CardSuits::Trumps)]), Some(0)))]
         case: (Vec<Card>, Option<usize>),
     ) {
         let (played_cards, expected_index) = case;
@@ -28,30 +30,55 @@ mod trick {
         );
     }
 
+    #[rstest]
+    #[case(3, 0, 4)]
+    #[case(2, 3, 4)]
+    #[case(4, 0, 5)]
+    fn trick_winner_is_a_seat_of_the_table(
+        #[case] leader: usize,
+        #[case] winner: usize,
+        #[case] n_players: usize,
+    ) {
+        // A partial trick, the second card played wins it
+        let trick = Trick {
+            played_cards: Vec::from([
+                Card::new(2, CardSuits::Clubs),
+                Card::new(14, CardSuits::Clubs),
+            ]),
+            leader,
+        };
+        assert_eq!(trick.winner(n_players), Some(winner));
+    }
+
     #[rstest]
     fn check_selected_card_in_his_context(
         #[values(
-            (Vec::new(), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Hearts), Err("Selected card is not allowed to be played")),
-            (Vec::new(), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Clubs), Ok(true)),
-            (Vec::from([Card::new(8, CardSuits::Clubs)]), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Clubs), Ok(true)),
-            (Vec::from([Card::new(8, CardSuits::Clubs)]), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Hearts), Err("Selected card is not allowed to be played")),
-            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Hearts), Err("Selected card is not allowed to be played")),
-            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Trumps), Ok(true)),
-            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(14, CardSuits::Trumps), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Trumps), Ok(true)),
-            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(14, CardSuits::Trumps), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Trumps), Err("Selected card is not allowed to be played")),
-            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Hearts), Ok(true)),
+            (Vec::new(), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Hearts), false),
+            (Vec::new(), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Clubs), true),
+            (Vec::from([Card::new(8, CardSuits::Clubs)]), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Clubs), true),
+            (Vec::from([Card::new(8, CardSuits::Clubs)]), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Hearts), false),
+            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Hearts), false),
+            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Trumps), true),
+            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(14, CardSuits::Trumps), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Trumps), true),
+            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(14, CardSuits::Trumps), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Trumps), false),
+            (Vec::from([Card::new(8, CardSuits::Trumps)]), Vec::from([Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Hearts), true),
+            (Vec::from([Card::new(8, CardSuits::Clubs)]), Vec::from([Card::new(14, CardSuits::Clubs), Card::new(22, CardSuits::Trumps)]), Card::new(22, CardSuits::Trumps), true),
+            (Vec::from([Card::new(8, CardSuits::Clubs), Card::new(9, CardSuits::Trumps)]), Vec::from([Card::new(2, CardSuits::Trumps), Card::new(12, CardSuits::Trumps), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Trumps), false),
+            (Vec::from([Card::new(22, CardSuits::Trumps), Card::new(8, CardSuits::Hearts)]), Vec::from([Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Hearts)]), Card::new(2, CardSuits::Trumps), false),
         )]
         // (Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Hearts), None, true),
         // (Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Hearts), Some(CardSuits::Trumps), false),
         // (Vec::from([Card::new(14, CardSuits::Clubs), Card::new(2, CardSuits::Trumps), Card::new(2, CardSuits::Clubs), Card::new(2, CardSuits::Hearts)]), Card::new(14, CardSuits::Hearts), Some(CardSuits::Trumps), false))]
-        case: (Vec<Card>, Vec<Card>, Card, Result<bool, &str>),
+        case: (Vec<Card>, Vec<Card>, Card, bool),
     ) {
-        let (played_cards, player_cards, selected_card, expected_result) = case;
-        let mut trick = Trick::default();
-        trick.played_cards = played_cards;
+        let (played_cards, player_cards, selected_card, expected_allowed) = case;
+        let trick = Trick {
+            played_cards,
+            ..Default::default()
+        };
         assert_eq!(
-            check_selected_card(&trick, &player_cards, &selected_card),
-            expected_result
+            check_selected_card(&trick, &player_cards, &selected_card).is_ok(),
+            expected_allowed
         );
     }
 }
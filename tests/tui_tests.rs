@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod tui {
+    use rstest::rstest;
+    use tarot_cli::{
+        common::{
+            bid::Bids,
+            card::{Card, CardSuits},
+            deal::Deal,
+            event::Event,
+            game::Game,
+            phase::Phase,
+            score::DealScore,
+        },
+        tui::{
+            app::{label, sorted, App},
+            screen::{draw, positions, Positions},
+        },
+    };
+
+    use crate::common::play_until;
+
+    fn deal_in_play() -> Deal {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| deal.phase == Phase::Play);
+        deal
+    }
+
+    #[rstest]
+    #[case(Card::new(14, CardSuits::Hearts), "K♥")]
+    #[case(Card::new(12, CardSuits::Clubs), "C♣")]
+    #[case(Card::new(7, CardSuits::Spades), "7♠")]
+    #[case(Card::new(21, CardSuits::Trumps), "21*")]
+    #[case(Card::new(22, CardSuits::Trumps), "Fool")]
+    fn labels_cards(#[case] card: Card, #[case] expected: &str) {
+        assert_eq!(label(&card), expected);
+    }
+
+    #[test]
+    fn groups_the_hand_by_suit() {
+        let hand = [
+            Card::new(5, CardSuits::Trumps),
+            Card::new(3, CardSuits::Spades),
+            Card::new(14, CardSuits::Clubs),
+            Card::new(1, CardSuits::Clubs),
+        ];
+        assert_eq!(sorted(&hand), [hand[3], hand[2], hand[1], hand[0]]);
+    }
+
+    #[rstest]
+    #[case(3, 1, vec![(1, Positions::Bottom), (2, Positions::Left), (0, Positions::Right)])]
+    #[case(4, 0, vec![(0, Positions::Bottom), (1, Positions::Left), (2, Positions::Top), (3, Positions::Right)])]
+    fn seats_the_player_at_the_bottom(
+        #[case] n_players: usize,
+        #[case] seat: usize,
+        #[case] expected: Vec<(usize, Positions)>,
+    ) {
+        assert_eq!(positions(n_players, seat), expected);
+    }
+
+    #[test]
+    fn only_selects_legal_cards() {
+        let deal = deal_in_play();
+        let view = deal.view(deal.current);
+        let mut app = App::default();
+        let choices = App::choices(&view);
+        assert_eq!(choices.len(), view.hand.len());
+        for _ in 0..choices.len() {
+            match app.selected(&view) {
+                Some(action) => assert!(view.legal_actions.contains(&action)),
+                None => assert!(!view.legal_actions.contains(&choices[app.cursor])),
+            }
+            app.move_cursor(&view, 1);
+        }
+        // The cursor wraps around the hand
+        assert_eq!(app.cursor, 0);
+        app.move_cursor(&view, -1);
+        assert_eq!(app.cursor, choices.len() - 1);
+    }
+
+    #[test]
+    fn keeps_the_running_scores() {
+        let mut app = App::default();
+        let players: Vec<String> = ["Ann", "Bob", "Cid"].map(String::from).to_vec();
+        app.record(&Event::DealStarted {
+            players: players.clone(),
+        });
+        app.record(&Event::BidMade {
+            seat: 1,
+            bid: Bids::Garde,
+        });
+        let score = DealScore {
+            bid: Bids::Garde,
+            scores: vec![-30.0, 60.0, -30.0],
+            ..Default::default()
+        };
+        app.record(&Event::DealScored {
+            score: score.clone(),
+        });
+        // The next deal starts with another player
+        app.record(&Event::DealStarted {
+            players: [&players[1..], &players[..1]].concat(),
+        });
+        app.record(&Event::DealScored { score });
+
+        assert_eq!(app.deals, 2);
+        assert_eq!(
+            app.scores,
+            [
+                (String::from("Ann"), -60.0),
+                (String::from("Bob"), 30.0),
+                (String::from("Cid"), 30.0)
+            ]
+        );
+        assert!(app.log.contains(&String::from("Bob: Garde")));
+    }
+
+    #[test]
+    fn draws_the_table() {
+        let deal = deal_in_play();
+        let mut app = App::default();
+        app.record(&Event::DealStarted {
+            players: deal.record.players.clone(),
+        });
+        let mut screen = Vec::new();
+        draw(&mut screen, &deal.view(0), &app).unwrap();
+        let screen = String::from_utf8(screen).unwrap();
+        for name in &deal.record.players {
+            assert!(screen.contains(name.as_str()));
+        }
+        assert!(screen.contains("Your hand:"));
+        assert!(screen.contains("Auction"));
+        let (seat, bid) = deal.record.auction[0];
+        assert!(screen.contains(&format!("{}: {}", deal.record.players[seat], bid)));
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod common;
+
+#[cfg(test)]
+mod view {
+    use rstest::rstest;
+    use tarot_cli::common::{action::Action, bid::Bids, deal::Deal, game::Game, phase::Phase};
+
+    use crate::common::play_until;
+
+    fn auction(deal: &mut Deal, bids: [Bids; 4]) {
+        for bid in bids {
+            deal.apply(Action::Bid(bid)).unwrap();
+        }
+    }
+
+    #[rstest]
+    fn only_shows_the_seat_hand(#[values(0, 1, 2, 3)] seat: usize) {
+        let mut game = Game::default();
+        let deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        let view = deal.view(seat);
+
+        assert_eq!(view.hand, deal.players[seat].hand.cards);
+        assert_eq!(view.kitty, None);
+        assert_eq!(view.players.len(), 4);
+        assert_eq!(view.is_turn(), seat == 0);
+        assert_eq!(view.legal_actions.is_empty(), seat != 0);
+    }
+
+    #[test]
+    fn reveals_the_kitty_of_a_garde_to_everybody() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        let kitty = deal.kitty.cards.clone();
+        auction(
+            &mut deal,
+            [Bids::Passe, Bids::Garde, Bids::Passe, Bids::Passe],
+        );
+
+        for seat in 0..4 {
+            let view = deal.view(seat);
+            assert_eq!(view.kitty.as_ref(), Some(&kitty));
+            assert_eq!(view.contract(), Bids::Garde);
+            assert_eq!(view.auction.len(), 4);
+        }
+    }
+
+    #[test]
+    fn keeps_the_ecart_for_the_taker() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        auction(
+            &mut deal,
+            [Bids::Garde, Bids::Passe, Bids::Passe, Bids::Passe],
+        );
+        let action = deal.legal_actions()[0];
+        deal.apply(action).unwrap();
+
+        assert_eq!(deal.view(0).ecart.len(), 1);
+        for seat in 1..4 {
+            assert!(deal.view(seat).ecart.is_empty());
+        }
+    }
+
+    #[test]
+    fn hides_the_kitty_of_a_garde_sans() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        auction(
+            &mut deal,
+            [Bids::GardeSans, Bids::Passe, Bids::Passe, Bids::Passe],
+        );
+
+        assert_eq!(deal.phase, Phase::Declarations);
+        for seat in 0..4 {
+            assert_eq!(deal.view(seat).kitty, None);
+        }
+    }
+
+    #[test]
+    fn shows_the_played_cards() {
+        let mut game = Game::default();
+        let mut deal = Deal::new(&mut game.players, &mut game.deck).unwrap();
+        play_until(&mut deal, |deal| !deal.tricks.is_empty());
+
+        let view = deal.view(2);
+        assert_eq!(view.tricks.len(), 1);
+        assert_eq!(view.tricks[0].played_cards.len(), 4);
+        assert!(view.tricks[0]
+            .played_cards
+            .iter()
+            .all(|card| !view.hand.contains(card)));
+    }
+}